@@ -20,8 +20,8 @@ import {Position} from "v4-core/libraries/Position.sol";
 contract ArenaController {
     PoolManager immutable poolManager;
     PoolModifyLiquidityTest immutable router;
-    PoolSwapTest immutable swapRouter;
-    LiquidExchange immutable lex;
+    PoolSwapTest public immutable swapRouter;
+    LiquidExchange public immutable lex;
     Fetcher immutable fetcher;
 
     ArenaToken immutable currency0;
@@ -44,6 +44,11 @@ contract ArenaController {
         uint256 lexPrice;
         PoolKey pool;
         address fetcher;
+        uint128 liquidity;
+        uint256 feeGrowthGlobal0X128;
+        uint256 feeGrowthGlobal1X128;
+        uint24 protocolFee;
+        uint24 lpFee;
     }
 
     constructor(uint256 fee, uint256 initialPrice) {
@@ -70,16 +75,20 @@ contract ArenaController {
     }
 
     function constructSignal() public view returns (Signal memory) {
-        (uint160 sqrtPriceX96, int24 tick,,) = fetcher.getSlot0(poolManager, fetcher.toId(poolKey));
-
-        return Signal({
-            currentTick: tick,
-            sqrtPriceX96: sqrtPriceX96,
-            manager: address(poolManager),
-            lexPrice: lex.price(),
-            pool: poolKey,
-            fetcher: address(fetcher)
-        });
+        Signal memory signal;
+        PoolId id = fetcher.toId(poolKey);
+
+        // the fee charged by a dynamic fee pool is its lpFee, while its key only holds the dynamic fee flag
+        (signal.sqrtPriceX96, signal.currentTick, signal.protocolFee, signal.lpFee) = fetcher.getSlot0(poolManager, id);
+        (signal.feeGrowthGlobal0X128, signal.feeGrowthGlobal1X128) = fetcher.getFeeGrowthGlobals(poolManager, id);
+        signal.liquidity = fetcher.getLiquidity(poolManager, id);
+
+        signal.manager = address(poolManager);
+        signal.lexPrice = lex.price();
+        signal.pool = poolKey;
+        signal.fetcher = address(fetcher);
+
+        return signal;
     }
 
     function setPrice(uint256 price) public {
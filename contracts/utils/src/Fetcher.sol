@@ -9,6 +9,8 @@ import {Position} from "v4-core/libraries/Position.sol";
 // https://github.com/Uniswap/v4-core/blob/799dd2cb980319a8d3b827b6a7aa59a606634553/src/libraries/StateLibrary.sol
 contract Fetcher {
     bytes32 public constant POOLS_SLOT = bytes32(uint256(6));
+    uint256 public constant FEE_GROWTH_GLOBAL0_OFFSET = 1;
+    uint256 public constant LIQUIDITY_OFFSET = 3;
     uint256 public constant TICKS_OFFSET = 4;
 
     function _getPoolStateSlot(PoolId poolId) internal pure returns (bytes32) {
@@ -69,6 +71,30 @@ contract Fetcher {
         }
     }
 
+    function getFeeGrowthGlobals(IPoolManager manager, PoolId poolId)
+        external
+        view
+        returns (uint256 feeGrowthGlobal0X128, uint256 feeGrowthGlobal1X128)
+    {
+        // slot key of Pool.State value: `pools[poolId]`
+        bytes32 stateSlot = _getPoolStateSlot(poolId);
+
+        // Pool.State, `uint256 feeGrowthGlobal0X128` followed by `uint256 feeGrowthGlobal1X128`
+        bytes32[] memory data = manager.extsload(bytes32(uint256(stateSlot) + FEE_GROWTH_GLOBAL0_OFFSET), 2);
+        assembly ("memory-safe") {
+            feeGrowthGlobal0X128 := mload(add(data, 32))
+            feeGrowthGlobal1X128 := mload(add(data, 64))
+        }
+    }
+
+    function getLiquidity(IPoolManager manager, PoolId poolId) external view returns (uint128 liquidity) {
+        // slot key of Pool.State value: `pools[poolId]`
+        bytes32 stateSlot = _getPoolStateSlot(poolId);
+
+        // Pool.State: `uint128 liquidity`
+        liquidity = uint128(uint256(manager.extsload(bytes32(uint256(stateSlot) + LIQUIDITY_OFFSET))));
+    }
+
     function _getTickInfoSlot(PoolId poolId, int24 tick) internal pure returns (bytes32) {
         // slot key of Pool.State value: `pools[poolId]`
         bytes32 stateSlot = _getPoolStateSlot(poolId);
@@ -0,0 +1,212 @@
+use async_trait::async_trait;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{Distribution, Exp};
+
+use super::{noise::SizeDistribution, *};
+use crate::engine::mempool::Action;
+
+/// Self-exciting point process with an exponential kernel, whose intensity is `baseline + sum(excitation * exp(-decay * (t - t_i)))` over past arrivals `t_i`.
+///
+/// The process is stationary while `excitation < decay`.
+#[derive(Debug, Clone)]
+pub struct HawkesProcess {
+    /// Baseline intensity, in arrivals per unit of simulated time.
+    pub baseline: f64,
+
+    /// Jump in intensity caused by each arrival.
+    pub excitation: f64,
+
+    /// Rate at which the excess intensity decays back to the baseline.
+    pub decay: f64,
+
+    /// Current intensity above the baseline.
+    excess: f64,
+}
+
+impl HawkesProcess {
+    /// Public constructor function for a new [`HawkesProcess`], starting at its baseline intensity.
+    pub fn new(baseline: f64, excitation: f64, decay: f64) -> Self {
+        Self {
+            baseline,
+            excitation,
+            decay,
+            excess: 0.0,
+        }
+    }
+
+    /// Returns the current intensity of the process.
+    pub fn intensity(&self) -> f64 {
+        self.baseline + self.excess
+    }
+
+    /// Add an exogenous jump to the intensity, such as one caused by a large price move.
+    pub fn excite(&mut self, jump: f64) {
+        self.excess += jump.max(0.0);
+    }
+
+    /// Simulate arrivals over the next `dt` units of simulated time using Ogata's thinning algorithm, returning their offsets into the interval.
+    pub fn simulate<R: Rng>(&mut self, dt: f64, rng: &mut R) -> Vec<f64> {
+        let mut arrivals = Vec::new();
+        let mut elapsed = 0.0;
+
+        loop {
+            // the intensity only decays between arrivals, so its current value bounds it until the next one
+            let bound = self.intensity();
+
+            let Ok(waiting) = Exp::new(bound) else {
+                break;
+            };
+
+            let wait = waiting.sample(rng);
+
+            if elapsed + wait > dt {
+                self.excess *= (-self.decay * (dt - elapsed)).exp();
+                break;
+            }
+
+            elapsed += wait;
+            self.excess *= (-self.decay * wait).exp();
+
+            if rng.gen::<f64>() * bound <= self.intensity() {
+                arrivals.push(elapsed);
+                self.excess += self.excitation;
+            }
+        }
+
+        arrivals
+    }
+}
+
+/// Implementation of an [`Agent`] whose swap arrivals follow a [`HawkesProcess`], so that trades cluster in bursts.
+///
+/// Arrivals are simulated over the feed's simulated time between steps, and each one submits a swap to the step's mempool.
+/// Large moves of the feed can optionally excite the process, so that volume and fee income co-move with volatility.
+pub struct HawkesTrader {
+    /// The process driving swap arrivals.
+    pub process: HawkesProcess,
+
+    /// Distribution of the size of each swap.
+    pub size: SizeDistribution,
+
+    /// Probability that a swap buys token0 from the pool, rather than selling it.
+    pub buy_probability: f64,
+
+    /// Relative move of the feed between steps above which the process is excited.
+    pub move_threshold: f64,
+
+    /// Jump in intensity caused by a feed move above the threshold.
+    pub move_excitation: f64,
+
+    /// Value and time of the feed on the previous step.
+    last: Option<(f64, f64)>,
+
+    rng: StdRng,
+}
+
+impl HawkesTrader {
+    /// Public constructor function for a new [`HawkesTrader`], seeding its random number generator.
+    pub fn new(
+        process: HawkesProcess,
+        size: SizeDistribution,
+        buy_probability: f64,
+        seed: u64,
+    ) -> Self {
+        Self {
+            process,
+            size,
+            buy_probability,
+            move_threshold: f64::INFINITY,
+            move_excitation: 0.0,
+            last: None,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Excite the process by `excitation` whenever the feed moves by more than `threshold`, relative to its previous value.
+    pub fn with_price_excitation(mut self, threshold: f64, excitation: f64) -> Self {
+        self.move_threshold = threshold;
+        self.move_excitation = excitation;
+        self
+    }
+}
+
+#[async_trait]
+impl<V> Agent<V> for HawkesTrader {
+    async fn init(
+        &mut self,
+        _provider: AnvilProvider,
+        signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        _engine: Engine,
+    ) {
+        self.last = Some((signal.current_value, signal.time));
+    }
+
+    async fn act(
+        &mut self,
+        provider: AnvilProvider,
+        signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        engine: Engine,
+    ) {
+        let (last_value, last_time) = self
+            .last
+            .replace((signal.current_value, signal.time))
+            .unwrap_or((signal.current_value, signal.time));
+
+        if last_value != 0.0
+            && ((signal.current_value - last_value) / last_value).abs() > self.move_threshold
+        {
+            self.process.excite(self.move_excitation);
+        }
+
+        let arrivals = self
+            .process
+            .simulate(signal.time - last_time, &mut self.rng);
+
+        for _ in arrivals {
+            let buy = self.rng.gen_bool(self.buy_probability.clamp(0.0, 1.0));
+            let size = self.size.sample(&mut self.rng);
+
+            let amount_in = Uint::from((size * 1e18) as u128);
+
+            if amount_in.is_zero() {
+                continue;
+            }
+
+            engine.submit(
+                Action::Swap {
+                    zero_for_one: !buy,
+                    amount_in,
+                    sqrt_price_limit_x96: None,
+                    hook_data: Bytes::new(),
+                },
+                provider.clone(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hawkes_process_clusters_arrivals() {
+        let mut rng = StdRng::seed_from_u64(0);
+
+        let mut poisson = HawkesProcess::new(5.0, 0.0, 1.0);
+        let baseline: usize = (0..1000)
+            .map(|_| poisson.simulate(1.0, &mut rng).len())
+            .sum();
+
+        let mut hawkes = HawkesProcess::new(5.0, 0.5, 1.0);
+        let excited: usize = (0..1000)
+            .map(|_| hawkes.simulate(1.0, &mut rng).len())
+            .sum();
+
+        // stationary mean intensity is baseline / (1 - excitation / decay)
+        assert!((baseline as f64 / 1000.0 - 5.0).abs() < 0.3);
+        assert!((excited as f64 / 1000.0 - 10.0).abs() < 1.0);
+    }
+}
@@ -0,0 +1,82 @@
+use alloy::primitives::{utils::parse_ether, U256};
+use async_trait::async_trait;
+
+use super::*;
+use crate::engine::{math::optimal_arbitrage, mempool::Action};
+
+/// Implementation of an [`Agent`] that trades ahead of the LEX price, modelling toxic order flow.
+///
+/// Each step it sees the value the feed will take `horizon` steps later, before it is set on the liquid exchange, and swaps the pool towards it.
+/// Swaps are sized with the closed-form optimal swap against the future price, so the trader extracts as much as the pool's fee allows, capped by `max_amount_in`.
+pub struct InformedTrader {
+    /// Number of steps ahead of the feed the trader sees.
+    pub horizon: usize,
+
+    /// Maximum amount of the input token swapped on each step.
+    pub max_amount_in: U256,
+}
+
+impl InformedTrader {
+    /// Public constructor function for a new [`InformedTrader`].
+    pub fn new(horizon: usize, max_amount_in: U256) -> Self {
+        Self {
+            horizon: horizon.max(1),
+            max_amount_in,
+        }
+    }
+}
+
+#[async_trait]
+impl<V> Agent<V> for InformedTrader {
+    async fn init(
+        &mut self,
+        _provider: AnvilProvider,
+        _signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        _engine: Engine,
+    ) {
+    }
+
+    async fn act(
+        &mut self,
+        provider: AnvilProvider,
+        signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        engine: Engine,
+    ) {
+        let Some(future) = self
+            .horizon
+            .checked_sub(1)
+            .and_then(|ahead| signal.forecast.get(ahead))
+        else {
+            return;
+        };
+
+        let Ok(future_price) = parse_ether(&future.to_string()) else {
+            return;
+        };
+
+        let Some(swap) = optimal_arbitrage(
+            U256::from(signal.sqrt_price_x96),
+            future_price,
+            signal.liquidity,
+            signal.lp_fee.to(),
+        ) else {
+            return;
+        };
+
+        engine.submit(
+            Action::Swap {
+                zero_for_one: swap.zero_for_one,
+                amount_in: swap.amount_in.min(self.max_amount_in),
+                sqrt_price_limit_x96: Some(swap.sqrt_price_limit_x96.to()),
+                hook_data: Bytes::new(),
+            },
+            provider,
+        );
+    }
+
+    fn lookahead(&self) -> usize {
+        self.horizon
+    }
+}
@@ -0,0 +1,198 @@
+use alloy::{
+    primitives::{Signed, I256, U256},
+    providers::WalletProvider,
+};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use super::*;
+use crate::engine::{
+    math::{usable_tick_range, FEE_DENOMINATOR},
+    mempool::{Action, PendingTransaction},
+    record::Record,
+};
+
+/// Record of liquidity provided just in time for a single pending swap.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LiquidityRecord {
+    /// Step of the simulation at which the liquidity was provided.
+    pub step: Option<usize>,
+
+    /// Lower tick of the position.
+    pub tick_lower: i32,
+
+    /// Upper tick of the position.
+    pub tick_upper: i32,
+
+    /// Liquidity added before the swap and removed after it.
+    pub liquidity: u128,
+
+    /// Whether the swap sold token0 to the pool.
+    pub zero_for_one: bool,
+
+    /// Input amount of the swap.
+    pub swap_amount_in: U256,
+
+    /// Estimated share of the swap's fee earned by the position, in the swap's input token.
+    ///
+    /// The estimate assumes that the swap stays within the position's range and that no other liquidity is added or removed before it, so it overstates the fees of swaps that cross out of the range.
+    pub estimated_fees: U256,
+}
+
+/// Implementation of an [`Agent`] that provides just-in-time liquidity to large pending swaps.
+///
+/// While searching, it picks the largest pending swap from another account and brackets it with a concentrated position around the current tick, added right before the swap and removed right after it.
+/// Positions are held by the controller's liquidity router and pooled with every other position over the same range, so the fee capture is estimated from the swap's fee and the position's share of the active liquidity, assuming the swap stays within the range.
+/// Each estimate is reported through the [`Engine`] as a `liquidity` [`Record`], which the [`Arena`](crate::Arena) logs to its inspector alongside the strategies' records, and kept in the ledger.
+pub struct JitLiquidityProvider {
+    /// Liquidity added for each swap.
+    pub liquidity: u128,
+
+    /// Number of tick spacings the position extends on either side of the current one.
+    pub width: i32,
+
+    /// Minimum input amount of a pending swap for liquidity to be provided to it.
+    pub min_swap_amount: U256,
+
+    /// Records of every swap liquidity was provided to.
+    pub ledger: Vec<LiquidityRecord>,
+}
+
+impl JitLiquidityProvider {
+    /// Public constructor function for a new [`JitLiquidityProvider`].
+    pub fn new(liquidity: u128, width: i32, min_swap_amount: U256) -> Self {
+        Self {
+            liquidity,
+            width: width.max(0),
+            min_swap_amount,
+            ledger: Vec::new(),
+        }
+    }
+}
+
+#[async_trait]
+impl<V> Agent<V> for JitLiquidityProvider {
+    async fn init(
+        &mut self,
+        _provider: AnvilProvider,
+        _signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        _engine: Engine,
+    ) {
+    }
+
+    async fn act(
+        &mut self,
+        _provider: AnvilProvider,
+        _signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        _engine: Engine,
+    ) {
+    }
+
+    async fn search(
+        &mut self,
+        provider: AnvilProvider,
+        signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        engine: Engine,
+    ) {
+        let provider_address = provider.default_signer_address();
+
+        let Some((index, zero_for_one, swap_amount_in)) = engine
+            .mempool()
+            .pending()
+            .iter()
+            .enumerate()
+            .filter(|(_, transaction)| transaction.sender != provider_address)
+            .filter_map(|(index, transaction)| match transaction.action {
+                Action::Swap {
+                    zero_for_one,
+                    amount_in,
+                    ..
+                } if amount_in >= self.min_swap_amount => Some((index, zero_for_one, amount_in)),
+                _ => None,
+            })
+            .max_by_key(|(_, _, amount_in)| *amount_in)
+        else {
+            return;
+        };
+
+        if self.liquidity == 0 {
+            return;
+        }
+
+        let spacing = signal.pool.tickSpacing.as_i32();
+        let base = signal.tick.as_i32().div_euclid(spacing) * spacing;
+
+        // near the ends of the price range the bracket is cut short at the last usable ticks
+        let (min_tick, max_tick) = usable_tick_range(spacing);
+        let tick_lower = base
+            .saturating_sub(self.width.saturating_mul(spacing))
+            .max(min_tick);
+        let tick_upper = base
+            .saturating_add(self.width.saturating_add(1).saturating_mul(spacing))
+            .min(max_tick);
+
+        let (Ok(lower), Ok(upper)) = (
+            Signed::<24, 1>::try_from(tick_lower),
+            Signed::<24, 1>::try_from(tick_upper),
+        ) else {
+            return;
+        };
+
+        if tick_lower >= tick_upper {
+            return;
+        }
+
+        let active = signal.liquidity;
+
+        let fee = U256::from(signal.lp_fee.to::<u32>());
+        let estimated_fees = swap_amount_in * fee / U256::from(FEE_DENOMINATOR)
+            * U256::from(self.liquidity)
+            / (U256::from(active) + U256::from(self.liquidity));
+
+        let liquidity = I256::try_from(self.liquidity).unwrap();
+
+        let modify = |liquidity_delta| Action::ModifyLiquidity {
+            liquidity_delta,
+            tick_lower: lower,
+            tick_upper: upper,
+            hook_data: Bytes::new(),
+        };
+
+        let mempool = engine.mempool();
+
+        mempool.insert(
+            index,
+            PendingTransaction::new(modify(liquidity), provider.clone()),
+        );
+        mempool.insert(
+            index + 2,
+            PendingTransaction::new(modify(-liquidity), provider),
+        );
+
+        let record = LiquidityRecord {
+            step: signal.step,
+            tick_lower,
+            tick_upper,
+            liquidity: self.liquidity,
+            zero_for_one,
+            swap_amount_in,
+            estimated_fees,
+        };
+
+        match serde_json::to_value(&record) {
+            Ok(value) => engine.report(Record::new(
+                signal.step,
+                signal.time,
+                "liquidity".to_string(),
+                value,
+            )),
+            Err(e) => error!(error = %e, "failed to serialize liquidity record"),
+        }
+
+        self.ledger.push(record);
+    }
+}
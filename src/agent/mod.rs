@@ -0,0 +1,63 @@
+use async_trait::async_trait;
+
+use super::*;
+
+/// Defines an agent that submits uninformed order flow.
+pub mod noise;
+
+/// Defines a self-exciting order flow generator.
+pub mod hawkes;
+
+/// Defines an agent that trades ahead of the price feed.
+pub mod informed;
+
+/// Defines an agent that sandwiches pending swaps.
+pub mod searcher;
+
+/// Defines an agent that provides just-in-time liquidity to pending swaps.
+pub mod jit;
+
+/// Represents a market participant, such as a trader, searcher or keeper, that can be run in an [`Arena`].
+///
+/// Unlike a [`Strategy`], an agent is stateful and not oriented around liquidity provision: it can swap on the pool, trade on the liquid exchange and modify liquidity through the [`Engine`].
+/// Each agent trades from its own account and logs to its own [`Inspector`].
+///
+/// Transactions that agents [`Engine::submit`] while acting are queued in the step's [`Mempool`](crate::engine::mempool::Mempool), which every agent may then inspect and reorder in [`Agent::search`] before the queue is executed.
+#[async_trait]
+pub trait Agent<V>: Send {
+    /// Initialization function for the agent to be run upon simulation startup.
+    async fn init(
+        &mut self,
+        provider: AnvilProvider,
+        signal: Signal,
+        inspector: &mut Box<dyn Inspector<V>>,
+        engine: Engine,
+    );
+
+    /// Action function for the agent to be run each simulation step, after arbitrage.
+    async fn act(
+        &mut self,
+        provider: AnvilProvider,
+        signal: Signal,
+        inspector: &mut Box<dyn Inspector<V>>,
+        engine: Engine,
+    );
+
+    /// Search function for the agent to be run each simulation step, after every agent has acted and before the [`Mempool`](crate::engine::mempool::Mempool) is executed. Defaults to doing nothing.
+    async fn search(
+        &mut self,
+        _provider: AnvilProvider,
+        _signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        _engine: Engine,
+    ) {
+    }
+
+    /// Number of upcoming feed values the agent needs to see in [`Signal::forecast`]. Defaults to none.
+    fn lookahead(&self) -> usize {
+        0
+    }
+}
+
+/// An [`Agent`] paired with the [`Inspector`] that it logs its own metrics to.
+pub type Participant<V> = (Box<dyn Agent<V>>, Box<dyn Inspector<V>>);
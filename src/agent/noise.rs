@@ -0,0 +1,172 @@
+use async_trait::async_trait;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use rand_distr::{Distribution, LogNormal, Pareto, Poisson};
+
+use super::*;
+use crate::engine::mempool::Action;
+
+/// Distribution of the size of each noise trade, denominated in the input token.
+#[derive(Debug, Clone)]
+pub enum SizeDistribution {
+    /// Log-normally distributed sizes.
+    LogNormal {
+        /// Mean of the logarithm of the size.
+        mu: f64,
+
+        /// Standard deviation of the logarithm of the size.
+        sigma: f64,
+    },
+
+    /// Pareto distributed sizes, with a heavy right tail.
+    Pareto {
+        /// Minimum size.
+        scale: f64,
+
+        /// Tail index, smaller values giving heavier tails.
+        shape: f64,
+    },
+}
+
+impl SizeDistribution {
+    pub(crate) fn sample(&self, rng: &mut StdRng) -> f64 {
+        match *self {
+            SizeDistribution::LogNormal { mu, sigma } => LogNormal::new(mu, sigma)
+                .map(|distribution| distribution.sample(rng))
+                .unwrap_or(0.0),
+            SizeDistribution::Pareto { scale, shape } => Pareto::new(scale, shape)
+                .map(|distribution| distribution.sample(rng))
+                .unwrap_or(0.0),
+        }
+    }
+}
+
+/// Implementation of an [`Agent`] that submits uninformed swaps against the pool.
+///
+/// Each step the number of swaps is drawn from a Poisson distribution, and each swap's direction and size are drawn independently of the market. This is the order flow that liquidity providers earn most of their fees from.
+pub struct NoiseTrader {
+    /// Expected number of swaps per step.
+    pub arrival_rate: f64,
+
+    /// Distribution of the size of each swap.
+    pub size: SizeDistribution,
+
+    /// Probability that a swap buys token0 from the pool, rather than selling it.
+    pub buy_probability: f64,
+
+    rng: StdRng,
+}
+
+impl NoiseTrader {
+    /// Public constructor function for a new [`NoiseTrader`], seeding its random number generator.
+    pub fn new(arrival_rate: f64, size: SizeDistribution, buy_probability: f64, seed: u64) -> Self {
+        Self {
+            arrival_rate,
+            size,
+            buy_probability,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Draw the trades of one step, as whether each buys token0 and its size.
+    fn draw(&mut self) -> Vec<(bool, f64)> {
+        let arrivals = Poisson::new(self.arrival_rate)
+            .map(|distribution| distribution.sample(&mut self.rng) as usize)
+            .unwrap_or(0);
+
+        (0..arrivals)
+            .map(|_| {
+                let buy = self.rng.gen_bool(self.buy_probability.clamp(0.0, 1.0));
+                (buy, self.size.sample(&mut self.rng))
+            })
+            .collect()
+    }
+}
+
+#[async_trait]
+impl<V> Agent<V> for NoiseTrader {
+    async fn init(
+        &mut self,
+        _provider: AnvilProvider,
+        _signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        _engine: Engine,
+    ) {
+    }
+
+    async fn act(
+        &mut self,
+        provider: AnvilProvider,
+        _signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        engine: Engine,
+    ) {
+        for (buy, size) in self.draw() {
+            let amount_in = Uint::from((size * 1e18) as u128);
+
+            if amount_in.is_zero() {
+                continue;
+            }
+
+            engine.submit(
+                Action::Swap {
+                    zero_for_one: !buy,
+                    amount_in,
+                    sqrt_price_limit_x96: None,
+                    hook_data: Bytes::new(),
+                },
+                provider.clone(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn mean(samples: &[f64]) -> f64 {
+        samples.iter().sum::<f64>() / samples.len() as f64
+    }
+
+    #[test]
+    fn test_size_distribution_means() {
+        let mut rng = StdRng::seed_from_u64(7);
+
+        // the mean of a lognormal is exp(mu + sigma^2 / 2)
+        let lognormal = SizeDistribution::LogNormal {
+            mu: 0.0,
+            sigma: 0.5,
+        };
+        let samples: Vec<f64> = (0..100_000).map(|_| lognormal.sample(&mut rng)).collect();
+        assert!((mean(&samples) - 0.125f64.exp()).abs() < 0.01);
+
+        // the mean of a Pareto is shape * scale / (shape - 1)
+        let pareto = SizeDistribution::Pareto {
+            scale: 1.0,
+            shape: 3.0,
+        };
+        let samples: Vec<f64> = (0..100_000).map(|_| pareto.sample(&mut rng)).collect();
+        assert!((mean(&samples) - 1.5).abs() < 0.02);
+        assert!(samples.iter().all(|size| *size >= 1.0));
+    }
+
+    #[test]
+    fn test_noise_trader_draws() {
+        let size = SizeDistribution::LogNormal {
+            mu: 0.0,
+            sigma: 0.5,
+        };
+        let mut trader = NoiseTrader::new(4.0, size, 0.3, 7);
+
+        let trades: Vec<(bool, f64)> = (0..10_000).flat_map(|_| trader.draw()).collect();
+        let buys = trades.iter().filter(|(buy, _)| *buy).count();
+
+        assert!((trades.len() as f64 / 10_000.0 - 4.0).abs() < 0.1);
+        assert!((buys as f64 / trades.len() as f64 - 0.3).abs() < 0.01);
+
+        // draws are reproducible from the seed
+        let mut replay = NoiseTrader::new(4.0, trader.size.clone(), 0.3, 7);
+        let replayed: Vec<(bool, f64)> = (0..10_000).flat_map(|_| replay.draw()).collect();
+        assert_eq!(trades, replayed);
+    }
+}
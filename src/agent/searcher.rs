@@ -0,0 +1,138 @@
+use alloy::{primitives::U256, providers::WalletProvider};
+use async_trait::async_trait;
+
+use super::*;
+use crate::engine::{
+    math::swap_exact_input,
+    mempool::{Action, PendingTransaction},
+};
+
+/// Implementation of an [`Agent`] that sandwiches other participants' pending swaps.
+///
+/// While searching, it picks the largest pending swap from another account that has no price limit, and brackets it with a front-run in the same direction and a back-run that sells the front-run's proceeds straight back.
+/// The sandwich is only submitted if it is profitable against the current pool state, assuming the three swaps stay within the active tick.
+pub struct SandwichSearcher {
+    /// Amount of the input token swapped in each front-run.
+    pub amount_in: U256,
+
+    /// Minimum input amount of a pending swap for it to be sandwiched.
+    pub min_victim_amount: U256,
+}
+
+impl SandwichSearcher {
+    /// Public constructor function for a new [`SandwichSearcher`].
+    pub fn new(amount_in: U256, min_victim_amount: U256) -> Self {
+        Self {
+            amount_in,
+            min_victim_amount,
+        }
+    }
+}
+
+#[async_trait]
+impl<V> Agent<V> for SandwichSearcher {
+    async fn init(
+        &mut self,
+        _provider: AnvilProvider,
+        _signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        _engine: Engine,
+    ) {
+    }
+
+    async fn act(
+        &mut self,
+        _provider: AnvilProvider,
+        _signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        _engine: Engine,
+    ) {
+    }
+
+    async fn search(
+        &mut self,
+        provider: AnvilProvider,
+        signal: Signal,
+        _inspector: &mut Box<dyn Inspector<V>>,
+        engine: Engine,
+    ) {
+        let searcher = provider.default_signer_address();
+
+        let Some((index, zero_for_one, victim_amount)) = engine
+            .mempool()
+            .pending()
+            .iter()
+            .enumerate()
+            .filter(|(_, transaction)| transaction.sender != searcher)
+            .filter_map(|(index, transaction)| match transaction.action {
+                Action::Swap {
+                    zero_for_one,
+                    amount_in,
+                    sqrt_price_limit_x96: None,
+                    ..
+                } if amount_in >= self.min_victim_amount => Some((index, zero_for_one, amount_in)),
+                _ => None,
+            })
+            .max_by_key(|(_, _, amount_in)| *amount_in)
+        else {
+            return;
+        };
+
+        let liquidity = signal.liquidity;
+        let fee = signal.lp_fee.to();
+
+        let Some((front_run_out, front_run_price)) = swap_exact_input(
+            U256::from(signal.sqrt_price_x96),
+            liquidity,
+            fee,
+            zero_for_one,
+            self.amount_in,
+        ) else {
+            return;
+        };
+
+        let Some((_, victim_price)) =
+            swap_exact_input(front_run_price, liquidity, fee, zero_for_one, victim_amount)
+        else {
+            return;
+        };
+
+        let Some((back_run_out, _)) =
+            swap_exact_input(victim_price, liquidity, fee, !zero_for_one, front_run_out)
+        else {
+            return;
+        };
+
+        if back_run_out <= self.amount_in {
+            return;
+        }
+
+        let mempool = engine.mempool();
+
+        mempool.insert(
+            index,
+            PendingTransaction::new(
+                Action::Swap {
+                    zero_for_one,
+                    amount_in: self.amount_in,
+                    sqrt_price_limit_x96: None,
+                    hook_data: Bytes::new(),
+                },
+                provider.clone(),
+            ),
+        );
+
+        mempool.insert(
+            index + 2,
+            PendingTransaction::new(
+                Action::Swap {
+                    zero_for_one: !zero_for_one,
+                    amount_in: front_run_out,
+                    sqrt_price_limit_x96: None,
+                    hook_data: Bytes::new(),
+                },
+                provider,
+            ),
+        );
+    }
+}
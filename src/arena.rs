@@ -1,20 +1,37 @@
-use std::collections::HashMap;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
 
 use alloy::{
+    primitives::U256,
     providers::{Provider, ProviderBuilder, WalletProvider},
     signers::local::PrivateKeySigner,
 };
-use std::time::Instant;
+use rand::{rngs::StdRng, SeedableRng};
+use tracing::{field::display, info, info_span, instrument, warn, Instrument, Span};
+
 use super::*;
 use crate::{
+    agent::{Agent, Participant},
     config::Config,
-    engine::{arbitrageur::Arbitrageur, inspector::Inspector},
+    engine::{
+        arbitrageur::{Arbitrageur, Competition},
+        inspector::{FanoutInspector, Inspector, ScopedInspector},
+        mempool::{InclusionRecord, OrderingPolicy},
+        pool::{self, PoolState},
+        record::{Record, Value},
+    },
     error::ArenaError,
-    feed::Feed,
+    feed::{Feed, Lookahead},
     strategy::Strategy,
     types::controller::ArenaController,
 };
 
+/// Number of accounts the Anvil instance is spawned with. Account 0 administers the simulation, followed by one account per strategy, arbitrageur and agent.
+const ACCOUNTS: usize = 32;
+
 /// Represents an [`Arena`] that can be used to run a simulation and execute strategies.
 pub struct Arena<V> {
     /// The underlying Anvil execution environment.
@@ -26,21 +43,70 @@ pub struct Arena<V> {
     /// The feed that provides the current, theoretical value of the pool.
     pub feed: Box<dyn Feed>,
 
-    /// The inspector that is used to evaluate the performance of the strategies.
-    pub inspector: Box<dyn Inspector<V>>,
+    /// The inspector that is used to evaluate the performance of the strategies, shared with each strategy's scoped handle.
+    pub inspector: Arc<Mutex<Box<dyn Inspector<V>>>>,
+
+    /// Each strategy's [`ScopedInspector`] handle to [`Arena::inspector`].
+    scoped_inspectors: Vec<Box<dyn Inspector<V>>>,
+
+    /// The arbitrageurs that are used to peg the pool, each trading from its own account.
+    pub arbitrageurs: Vec<Box<dyn Arbitrageur>>,
+
+    /// The order in which the arbitrageurs act on each step.
+    pub competition: Competition,
 
-    /// The arbitrageur that is used to peg the pool.
-    pub arbitrageur: Box<dyn Arbitrageur>,
+    /// The agents that participate in the market, each trading from its own account and logging to its own inspector.
+    pub agents: Vec<Participant<V>>,
+
+    /// The policy used to order each step's block, if the pending transactions are mined into one block rather than executed one by one.
+    pub ordering: Option<Box<dyn OrderingPolicy>>,
+
+    /// The inspector the state of the market is recorded to on each step, if market recording is enabled.
+    pub market_recorder: Option<Box<dyn Inspector<Record>>>,
 
     providers: HashMap<usize, AnvilProvider>,
+
+    rng: StdRng,
+
+    /// Number of upcoming feed values included in each [`Signal`].
+    lookahead: usize,
 }
 
 #[allow(clippy::redundant_closure)]
 impl<V> Arena<V> {
     /// Run all strategies in the simulation with a given configuration.
+    ///
+    /// Every inspector is saved once the simulation ends, including when it fails part way, so that the steps run so far are kept.
     pub async fn run(&mut self, config: Config) -> Result<(), ArenaError> {
+        let result = self.simulate(config).await;
+        let saved = self.save();
+
+        result.and(saved)
+    }
+
+    /// Save the arena's inspector, the market recorder and every agent's inspector, returning the first error once all of them have been saved.
+    fn save(&mut self) -> Result<(), ArenaError> {
+        let mut saved = vec![self.inspector.lock().unwrap().save()];
+
+        if let Some(recorder) = self.market_recorder.as_mut() {
+            saved.push(recorder.save());
+        }
+
+        for (_, inspector) in self.agents.iter_mut() {
+            saved.push(inspector.save());
+        }
+
+        saved.into_iter().collect()
+    }
+
+    /// Deploy the pool, initialize every participant and run each step of the simulation.
+    async fn simulate(&mut self, config: Config) -> Result<(), ArenaError> {
         let admin_provider = self.providers[&0].clone();
 
+        // account 0 is the admin, followed by the strategies, the arbitrageurs and the agents
+        let arbitrageur_offset = 1 + self.strategies.len();
+        let agent_offset = arbitrageur_offset + self.arbitrageurs.len();
+
         let controller = ArenaController::deploy(
             admin_provider.clone(),
             config.manager_fee,
@@ -63,118 +129,51 @@ impl<V> Arena<V> {
             .await
             .map_err(|e| ArenaError::PendingTransactionError(e))?;
 
-        let engine = Engine {
-            controller: *controller.address(),
-        };
+        let engine = Engine::new(*controller.address());
 
         for (idx, strategy) in self.strategies.iter_mut().enumerate() {
             let strategy_provider = self.providers[&(idx + 1)].clone();
 
-            let signal = controller.constructSignal().call().await?._0;
-
-            let signal = Signal::new(
-                signal.lexPrice,
-                None,
-                signal.currentTick,
-                signal.sqrtPriceX96,
-                signal.manager,
-                signal.pool,
-                signal.fetcher,
-                self.feed.current_value(),
-                *controller.address(),
-            );
+            let signal =
+                Self::signal(&controller, self.feed.as_ref(), self.lookahead, None).await?;
 
             strategy
                 .init(
                     strategy_provider.clone(),
                     signal,
-                    &mut self.inspector,
+                    &mut self.scoped_inspectors[idx],
                     engine.clone(),
                 )
+                .instrument(info_span!("strategy", index = idx))
                 .await;
         }
 
-        let signal = controller.constructSignal().call().await?._0;
+        for (idx, (agent, inspector)) in self.agents.iter_mut().enumerate() {
+            let provider = self.providers[&(agent_offset + idx)].clone();
 
-        let signal = Signal::new(
-            signal.lexPrice,
-            None,
-            signal.currentTick,
-            signal.sqrtPriceX96,
-            signal.manager,
-            signal.pool,
-            signal.fetcher,
-            self.feed.current_value(),
-            *controller.address(),
-        );
+            let signal =
+                Self::signal(&controller, self.feed.as_ref(), self.lookahead, None).await?;
 
-        self.arbitrageur.init(&signal, admin_provider.clone()).await;
-
-        for step in 0..config.steps {
-            let instant = Instant::now();
-            controller
-                .setPrice(
-                    alloy::primitives::utils::parse_ether(&self.feed.step().to_string())
-                        .map_err(ArenaError::ConversionError)?,
-                )
-                .nonce(
-                    admin_provider
-                        .get_transaction_count(admin_provider.default_signer_address())
-                        .await
-                        .unwrap(),
-                )
-                .send()
-                .await
-                .map_err(ArenaError::ContractError)?
-                .watch()
-                .await
-                .map_err(|e| ArenaError::PendingTransactionError(e))?;
-
-            let signal = controller.constructSignal().call().await?._0;
-
-            let signal = Signal::new(
-                signal.lexPrice,
-                None,
-                signal.currentTick,
-                signal.sqrtPriceX96,
-                signal.manager,
-                signal.pool,
-                signal.fetcher,
-                self.feed.current_value(),
-                *controller.address(),
-            );
-
-            self.arbitrageur
-                .arbitrage(&signal, admin_provider.clone())
+            agent
+                .init(provider, signal, inspector, engine.clone())
+                .instrument(info_span!("agent", index = idx))
                 .await;
+        }
 
-            for (idx, strategy) in self.strategies.iter_mut().enumerate() {
-                let signal = controller.constructSignal().call().await?._0;
-
-                let signal = Signal::new(
-                    signal.lexPrice,
-                    Some(step),
-                    signal.currentTick,
-                    signal.sqrtPriceX96,
-                    signal.manager,
-                    signal.pool,
-                    signal.fetcher,
-                    self.feed.current_value(),
-                    *controller.address(),
-                );
+        let signal = Self::signal(&controller, self.feed.as_ref(), self.lookahead, None).await?;
 
-                strategy
-                    .process(
-                        self.providers[&(idx + 1)].clone(),
-                        signal,
-                        &mut self.inspector,
-                        engine.clone(),
-                    )
-                    .await;
-            }
+        for (idx, arbitrageur) in self.arbitrageurs.iter_mut().enumerate() {
+            let provider = self.providers[&(arbitrageur_offset + idx)].clone();
+
+            arbitrageur
+                .init(&signal, provider)
+                .instrument(info_span!("arbitrageur", index = idx))
+                .await;
+        }
 
-            self.feed.step();
-            println!("Step {} took {:?}", step, instant.elapsed());
+        for step in 0..config.steps {
+            self.step(step, &controller, &engine, &admin_provider)
+                .await?;
         }
 
         // controller
@@ -188,6 +187,317 @@ impl<V> Arena<V> {
 
         Ok(())
     }
+
+    /// Advance the feed and run every participant for a single step.
+    #[instrument(name = "step", skip(self, controller, engine, admin_provider))]
+    async fn step(
+        &mut self,
+        step: usize,
+        controller: &ArenaController::ArenaControllerInstance<Http<Client>, AnvilProvider>,
+        engine: &Engine,
+        admin_provider: &AnvilProvider,
+    ) -> Result<(), ArenaError> {
+        let arbitrageur_offset = 1 + self.strategies.len();
+        let agent_offset = arbitrageur_offset + self.arbitrageurs.len();
+
+        let instant = Instant::now();
+
+        let price = self.feed.step();
+
+        Self::set_price(controller, admin_provider, price).await?;
+
+        let signal =
+            Self::signal(controller, self.feed.as_ref(), self.lookahead, Some(step)).await?;
+
+        self.record_market("pre_arbitrage", &signal);
+
+        let bids: Vec<u128> = self
+            .arbitrageurs
+            .iter_mut()
+            .map(|arbitrageur| arbitrageur.bid(&signal))
+            .collect();
+
+        for idx in self.arbitrage_order(&bids) {
+            let provider = self.providers[&(arbitrageur_offset + idx)].clone();
+
+            let signal =
+                Self::signal(controller, self.feed.as_ref(), self.lookahead, Some(step)).await?;
+
+            if let Some(mut record) = self.arbitrageurs[idx]
+                .arbitrage(&signal, provider)
+                .instrument(info_span!("arbitrageur", index = idx))
+                .await
+            {
+                record.arbitrageur = idx;
+                record.priority_fee = bids[idx];
+
+                let value = serde_json::to_value(&record)?;
+
+                self.inspector.lock().unwrap().log_record(
+                    Record::new(
+                        Some(step),
+                        signal.time,
+                        "arbitrage".to_string(),
+                        value.clone(),
+                    )
+                    .with_strategy(format!("arbitrageur-{}", idx)),
+                );
+
+                if let Some(recorder) = self.market_recorder.as_mut() {
+                    recorder.log(Record::new(
+                        Some(step),
+                        signal.time,
+                        format!("arbitrage/{}", idx),
+                        value,
+                    ));
+                }
+            }
+        }
+
+        let signal =
+            Self::signal(controller, self.feed.as_ref(), self.lookahead, Some(step)).await?;
+
+        self.record_market("post_arbitrage", &signal);
+
+        for (idx, (agent, inspector)) in self.agents.iter_mut().enumerate() {
+            let provider = self.providers[&(agent_offset + idx)].clone();
+
+            let signal =
+                Self::signal(controller, self.feed.as_ref(), self.lookahead, Some(step)).await?;
+
+            agent
+                .act(provider, signal, inspector, engine.clone())
+                .instrument(info_span!("agent", index = idx))
+                .await;
+
+            let mut main = self.inspector.lock().unwrap();
+
+            for mut record in engine.reports() {
+                record
+                    .strategy
+                    .get_or_insert_with(|| format!("agent-{}", idx));
+                main.log_record(record);
+            }
+        }
+
+        // when building blocks, strategies submit to the same block as the agents, and the block is ordered before searchers see it so that their bundles keep their place
+        if self.ordering.is_some() {
+            self.process_strategies(controller, engine, step).await?;
+
+            if let Some(ordering) = self.ordering.as_mut() {
+                engine.mempool().order(ordering.as_mut());
+            }
+        }
+
+        for (idx, (agent, inspector)) in self.agents.iter_mut().enumerate() {
+            let provider = self.providers[&(agent_offset + idx)].clone();
+
+            let signal =
+                Self::signal(controller, self.feed.as_ref(), self.lookahead, Some(step)).await?;
+
+            agent
+                .search(provider, signal, inspector, engine.clone())
+                .instrument(info_span!("agent", index = idx))
+                .await;
+
+            let mut main = self.inspector.lock().unwrap();
+
+            for mut record in engine.reports() {
+                record
+                    .strategy
+                    .get_or_insert_with(|| format!("agent-{}", idx));
+                main.log_record(record);
+            }
+        }
+
+        match self.ordering {
+            Some(_) => {
+                for record in engine
+                    .build_block(engine.mempool().drain(), step, admin_provider)
+                    .await?
+                {
+                    self.inspector.lock().unwrap().log_record(Record::new(
+                        Some(step),
+                        self.feed.current_time(),
+                        "inclusion".to_string(),
+                        serde_json::to_value(&record)?,
+                    ));
+                }
+            }
+            None => {
+                for (position, transaction) in engine.mempool().drain().into_iter().enumerate() {
+                    let sender = transaction.sender;
+                    let action = transaction.action.clone();
+                    let priority_fee = transaction.priority_fee;
+
+                    // a failed transaction is recorded like one left out of a block, rather than ending the simulation
+                    if let Err(e) = engine.execute(transaction).await {
+                        warn!(%sender, error = %e, "pending transaction failed");
+
+                        let record = InclusionRecord {
+                            step,
+                            position,
+                            sender,
+                            action,
+                            priority_fee,
+                            included: false,
+                            success: false,
+                            transaction_index: None,
+                            gas_used: 0,
+                            error: Some(e.to_string()),
+                        };
+
+                        self.inspector.lock().unwrap().log_record(Record::new(
+                            Some(step),
+                            self.feed.current_time(),
+                            "inclusion".to_string(),
+                            serde_json::to_value(&record)?,
+                        ));
+                    }
+                }
+
+                self.process_strategies(controller, engine, step).await?;
+            }
+        }
+
+        info!(elapsed = ?instant.elapsed(), "step complete");
+
+        Ok(())
+    }
+
+    /// Set the price of the liquid exchange to the latest value of the feed.
+    #[instrument(level = "debug", skip(controller, admin_provider), fields(tx_hash))]
+    async fn set_price(
+        controller: &ArenaController::ArenaControllerInstance<Http<Client>, AnvilProvider>,
+        admin_provider: &AnvilProvider,
+        price: f64,
+    ) -> Result<(), ArenaError> {
+        let call = controller
+            .setPrice(
+                alloy::primitives::utils::parse_ether(&price.to_string())
+                    .map_err(ArenaError::ConversionError)?,
+            )
+            .nonce(
+                admin_provider
+                    .get_transaction_count(admin_provider.default_signer_address())
+                    .await?,
+            );
+
+        let pending = call.send().await.map_err(ArenaError::ContractError)?;
+
+        Span::current().record("tx_hash", display(pending.tx_hash()));
+
+        pending
+            .watch()
+            .await
+            .map_err(|e| ArenaError::PendingTransactionError(e))?;
+
+        Ok(())
+    }
+
+    /// Record the state of the market to the market recorder, if there is one, and to the inspector through [`Inspector::log_market`], with each metric prefixed by `phase`.
+    fn record_market(&mut self, phase: &str, signal: &Signal) {
+        let metrics: [(&str, Value); 7] = [
+            ("feed_value", signal.current_value.into()),
+            ("lex_price", signal.exchange_price().into()),
+            ("pool_price", signal.pool_price().into()),
+            ("mispricing", signal.mispricing().into()),
+            ("tick", signal.tick.as_i64().into()),
+            ("sqrt_price_x96", U256::from(signal.sqrt_price_x96).into()),
+            ("liquidity", U256::from(signal.liquidity).into()),
+        ];
+
+        let mut inspector = self.inspector.lock().unwrap();
+
+        for (metric, value) in metrics {
+            let record = Record::new(
+                signal.step,
+                signal.time,
+                format!("{}/{}", phase, metric),
+                value,
+            );
+
+            if let Some(recorder) = self.market_recorder.as_mut() {
+                recorder.log(record.clone());
+            }
+
+            inspector.log_market(record);
+        }
+    }
+
+    /// Run the processing function of every strategy for this step.
+    async fn process_strategies(
+        &mut self,
+        controller: &ArenaController::ArenaControllerInstance<Http<Client>, AnvilProvider>,
+        engine: &Engine,
+        step: usize,
+    ) -> Result<(), ArenaError> {
+        for (idx, (strategy, inspector)) in self
+            .strategies
+            .iter_mut()
+            .zip(self.scoped_inspectors.iter_mut())
+            .enumerate()
+        {
+            let signal =
+                Self::signal(controller, self.feed.as_ref(), self.lookahead, Some(step)).await?;
+
+            strategy
+                .process(
+                    self.providers[&(idx + 1)].clone(),
+                    signal,
+                    inspector,
+                    engine.clone(),
+                )
+                .instrument(info_span!("strategy", index = idx))
+                .await;
+        }
+
+        Ok(())
+    }
+
+    /// Order in which the arbitrageurs act on this step, according to the competition model.
+    fn arbitrage_order(&mut self, bids: &[u128]) -> Vec<usize> {
+        self.competition.order(bids, &mut self.rng)
+    }
+
+    /// Construct a [`Signal`] from the current state of the pool and the price feed.
+    async fn signal(
+        controller: &ArenaController::ArenaControllerInstance<Http<Client>, AnvilProvider>,
+        feed: &dyn Feed,
+        lookahead: usize,
+        step: Option<usize>,
+    ) -> Result<Signal, ArenaError> {
+        let signal = controller.constructSignal().call().await?._0;
+
+        // `constructSignal` returns the pool's liquidity, fee growth and fees, but the compiled artifact predates them, so until it is rebuilt from `contracts/utils` they are read from the PoolManager's storage in one call
+        let state = PoolState::read(
+            signal.manager,
+            pool::pool_id(&signal.pool),
+            controller.provider(),
+        )
+        .await?;
+
+        Ok(Signal::new(
+            signal.lexPrice,
+            step,
+            feed.current_time(),
+            signal.currentTick,
+            signal.sqrtPriceX96,
+            state.liquidity,
+            state.fee_growth_global0_x128,
+            state.fee_growth_global1_x128,
+            state.protocol_fee,
+            state.lp_fee,
+            signal.manager,
+            signal.pool,
+            signal.fetcher,
+            feed.current_value(),
+            (1..=lookahead)
+                .filter_map(|ahead| feed.peek(ahead))
+                .collect(),
+            *controller.address(),
+        ))
+    }
 }
 
 /// A builder for an [`Arena`] that can be used to configure the simulation.
@@ -201,11 +511,23 @@ pub struct ArenaBuilder<V> {
     /// [`Arena::feed`]
     pub feed: Option<Box<dyn Feed>>,
 
-    /// [`Arena::inspector`]
-    pub inspector: Option<Box<dyn Inspector<V>>>,
+    /// The inspectors combined into [`Arena::inspector`].
+    pub inspectors: Vec<Box<dyn Inspector<V>>>,
 
-    /// [`Arena::arbitrageur`]
-    pub arbitrageur: Option<Box<dyn Arbitrageur>>,
+    /// [`Arena::arbitrageurs`]
+    pub arbitrageurs: Vec<Box<dyn Arbitrageur>>,
+
+    /// [`Arena::competition`]
+    pub competition: Competition,
+
+    /// [`Arena::agents`]
+    pub agents: Vec<Participant<V>>,
+
+    /// [`Arena::ordering`]
+    pub ordering: Option<Box<dyn OrderingPolicy>>,
+
+    /// [`Arena::market_recorder`]
+    pub market_recorder: Option<Box<dyn Inspector<Record>>>,
 }
 
 impl<V> Default for ArenaBuilder<V> {
@@ -218,11 +540,17 @@ impl<V> ArenaBuilder<V> {
     /// Public constructor function for a new [`ArenaBuilder`].
     pub fn new() -> Self {
         ArenaBuilder {
-            env: Anvil::default().spawn(),
+            env: Anvil::default()
+                .args(["--accounts", &ACCOUNTS.to_string()])
+                .spawn(),
             strategies: Vec::new(),
             feed: None,
-            inspector: None,
-            arbitrageur: None,
+            inspectors: Vec::new(),
+            arbitrageurs: Vec::new(),
+            competition: Competition::default(),
+            agents: Vec::new(),
+            ordering: None,
+            market_recorder: None,
         }
     }
 
@@ -238,23 +566,66 @@ impl<V> ArenaBuilder<V> {
         self
     }
 
-    /// Set the inspector that is used to evaluate the performance of the strategies.
+    /// Add an inspector that is used to evaluate the performance of the strategies. Every inspector receives everything the strategies and the runtime log.
     pub fn with_inspector(mut self, inspector: Box<dyn Inspector<V>>) -> Self {
-        self.inspector = Some(inspector);
+        self.inspectors.push(inspector);
         self
     }
 
-    /// Set the inspector that is used to evaluate the performance of the strategies.
+    /// Add an arbitrageur that is used to peg the pool. Each arbitrageur trades from its own account.
     pub fn with_arbitrageur(mut self, arbitrageur: Box<dyn Arbitrageur>) -> Self {
-        self.arbitrageur = Some(arbitrageur);
+        self.arbitrageurs.push(arbitrageur);
+        self
+    }
+
+    /// Add an agent that participates in the market, trading from its own account and logging to its own inspector.
+    pub fn with_agent(
+        mut self,
+        agent: Box<dyn Agent<V>>,
+        inspector: Box<dyn Inspector<V>>,
+    ) -> Self {
+        self.agents.push((agent, inspector));
+        self
+    }
+
+    /// Set the order in which competing arbitrageurs act on each step.
+    pub fn with_competition(mut self, competition: Competition) -> Self {
+        self.competition = competition;
+        self
+    }
+
+    /// Mine each step's pending transactions into a single block, in the order chosen by `ordering`, and log every inclusion outcome to the inspector.
+    ///
+    /// Strategies then process each step before the block is built, so that the transactions they submit are mined alongside the agents'. The policy orders the queue before agents search it, so that the transactions searchers insert around others are mined exactly where they were placed.
+    ///
+    /// Only transactions submitted through [`Engine::submit`] are queued. The `setPrice` call on the liquid exchange, the arbitrageurs' trades and any direct [`Engine::swap`], [`Engine::modify_liquidity`] or [`Engine::swap_on_lex`] call are still mined immediately, each into its own block, before the step's block is built.
+    pub fn with_block_building(mut self, ordering: Box<dyn OrderingPolicy>) -> Self {
+        self.ordering = Some(ordering);
+        self
+    }
+
+    /// Record the state of the market to a dedicated inspector on each step, such as a [`RecordWriter`](crate::engine::record::RecordWriter).
+    ///
+    /// The feed value, LEX and pool prices, mispricing, tick, `sqrtPriceX96` and active liquidity are recorded before and after arbitrage, prefixed with `pre_arbitrage/` and `post_arbitrage/`, along with every arbitrage trade as `arbitrage/{index}`.
+    pub fn with_market_recorder(mut self, recorder: Box<dyn Inspector<Record>>) -> Self {
+        self.market_recorder = Some(recorder);
         self
     }
 
     /// Build the [`Arena`] with the given configuration.
-    pub fn build(self) -> Arena<V> {
+    pub fn build(mut self) -> Arena<V>
+    where
+        V: Clone + 'static,
+    {
         let mut providers = HashMap::new();
 
-        for i in 0..9 {
+        assert!(
+            1 + self.strategies.len() + self.arbitrageurs.len() + self.agents.len()
+                <= self.env.keys().len(),
+            "not enough Anvil accounts for every strategy, arbitrageur and agent"
+        );
+
+        for i in 0..self.env.keys().len() {
             let signer: PrivateKeySigner = self.env.keys()[i].clone().into();
             let wallet = EthereumWallet::from(signer);
 
@@ -268,13 +639,54 @@ impl<V> ArenaBuilder<V> {
             providers.insert(i, provider);
         }
 
+        let lookahead = self
+            .agents
+            .iter()
+            .map(|(agent, _)| agent.lookahead())
+            .max()
+            .unwrap_or(0);
+
+        let feed = match lookahead {
+            0 => self.feed.unwrap(),
+            horizon => Box::new(Lookahead::new(self.feed.unwrap(), horizon)),
+        };
+
+        let inspector: Box<dyn Inspector<V>> = match self.inspectors.len() {
+            1 => self.inspectors.pop().unwrap(),
+            _ => Box::new(FanoutInspector::new(self.inspectors)),
+        };
+        let inspector = Arc::new(Mutex::new(inspector));
+
+        let scoped_inspectors = self
+            .strategies
+            .iter()
+            .enumerate()
+            .map(|(idx, strategy)| {
+                let scope = strategy
+                    .name()
+                    .unwrap_or_else(|| format!("strategy-{}", idx));
+
+                Box::new(ScopedInspector::new(scope, inspector.clone())) as Box<dyn Inspector<V>>
+            })
+            .collect();
+
         Arena {
             env: self.env,
             strategies: self.strategies,
-            feed: self.feed.unwrap(),
-            inspector: self.inspector.unwrap(),
-            arbitrageur: self.arbitrageur.unwrap(),
+            feed,
+            inspector,
+            scoped_inspectors,
+            arbitrageurs: self.arbitrageurs,
+            rng: StdRng::seed_from_u64(match self.competition {
+                Competition::Random(seed) => seed,
+                _ => 0,
+            }),
+            competition: self.competition,
+            agents: self.agents,
+            ordering: self.ordering,
+            market_recorder: self.market_recorder,
             providers,
+            lookahead,
         }
     }
 }
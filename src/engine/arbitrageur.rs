@@ -1,7 +1,17 @@
+use std::cmp::Reverse;
+
+use alloy::primitives::U512;
 use async_trait::async_trait;
+use rand::{rngs::StdRng, seq::SliceRandom, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use tracing::{debug, error, trace};
 
 use super::*;
-use crate::{types::controller::ArenaController, AnvilProvider, Signal};
+use crate::{
+    engine::math::{optimal_arbitrage, OptimalSwap, WAD},
+    types::controller::ArenaController,
+    AnvilProvider, Signal,
+};
 
 /// Generic trait allowing user defined arbitrage strategies.
 #[async_trait]
@@ -9,39 +19,528 @@ pub trait Arbitrageur {
     /// Initialize arbitrageur agent.
     async fn init(&mut self, signal: &Signal, provider: AnvilProvider);
 
-    /// Perform an arbitrage based on a [`Signal`].
-    async fn arbitrage(&mut self, signal: &Signal, provider: AnvilProvider);
+    /// Perform an arbitrage based on a [`Signal`], returning a record of the trade if one was made and priced.
+    async fn arbitrage(
+        &mut self,
+        signal: &Signal,
+        provider: AnvilProvider,
+    ) -> Option<ArbitrageRecord>;
+
+    /// Priority fee per unit of gas, in token1, bid for the right to act first under [`Competition::PriorityFeeAuction`].
+    fn bid(&mut self, _signal: &Signal) -> u128 {
+        0
+    }
+}
+
+/// Model of how competing arbitrageurs are ordered on each step of an [`Arena`](crate::arena::Arena).
+///
+/// Arbitrageurs act one after the other, each seeing the pool as left by the previous one, so the first to act captures most of the opportunity.
+#[derive(Debug, Clone, Default)]
+pub enum Competition {
+    /// Arbitrageurs act in the order they were added to the arena.
+    #[default]
+    FirstCome,
+
+    /// Arbitrageurs act in a uniformly random order each step, drawn from a generator with the given seed.
+    Random(u64),
+
+    /// Arbitrageurs act in descending order of their [`Arbitrageur::bid`], ties being broken by the order they were added.
+    PriorityFeeAuction,
+}
+
+impl Competition {
+    /// Order in which `bids.len()` arbitrageurs act on a step, given the bid of each.
+    pub(crate) fn order(&self, bids: &[u128], rng: &mut StdRng) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..bids.len()).collect();
+
+        match self {
+            Competition::FirstCome => {}
+            Competition::Random(_) => order.shuffle(rng),
+            Competition::PriorityFeeAuction => order.sort_by_key(|idx| Reverse(bids[*idx])),
+        }
+
+        order
+    }
 }
 
-/// Default implementation of an [`Arbitrageur`] that uses the closed-form optimal swap amount to determine the optimal arbitrage.
+/// Record of a single round trip arbitrage between the pool and the liquid exchange.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArbitrageRecord {
+    /// Step of the simulation at which the arbitrage was made.
+    pub step: Option<usize>,
+
+    /// Index of the arbitrageur that made the arbitrage, in the order it was added to the arena. Set by the runtime.
+    pub arbitrageur: usize,
+
+    /// Priority fee per unit of gas, in token1, that the arbitrageur bid on this step. Set by the runtime.
+    pub priority_fee: u128,
+
+    /// Whether token0 was sold to the pool and bought back on the LEX.
+    pub zero_for_one: bool,
+
+    /// Amount of the input token sold to the pool.
+    pub amount_in: U256,
+
+    /// Amount of the output token received from the pool and sold on the LEX.
+    pub amount_out: U256,
+
+    /// Amount of the input token bought back on the LEX. Zero for arbitrageurs that only trade on the pool.
+    pub amount_returned: U256,
+
+    /// Profit of the round trip in token1, before gas. For arbitrageurs that only trade on the pool, the output is valued at the LEX price instead of being sold.
+    pub profit: I256,
+
+    /// Gas used by every leg of the arbitrage.
+    pub gas_used: u128,
+
+    /// Cost of the gas used in token1. Zero for arbitrageurs that don't price gas.
+    pub gas_cost: U256,
+}
+
+/// Default implementation of an [`Arbitrageur`] that swaps a fixed amount towards the LEX price on each step.
+///
+/// The controller sizes the swap, so its record only holds the direction, the depth as the exact input or output, and the gas used.
 #[derive(Default)]
 pub struct FixedArbitrageur {
     /// The fixed amount to swap on each arbitrage opportunity.
     pub depth: Signed<256, 4>,
 }
 
+impl FixedArbitrageur {
+    #[instrument(level = "debug", skip_all, fields(depth = %self.depth, tx_hash))]
+    async fn equalize(
+        &self,
+        signal: &Signal,
+        provider: AnvilProvider,
+    ) -> Result<TransactionReceipt, ArenaError> {
+        let controller = ArenaController::new(signal.controller, provider.clone());
+
+        let call = controller
+            .equalizePrice(self.depth)
+            .nonce(nonce(&provider).await?);
+
+        let pending = call.send().await?;
+
+        Span::current().record("tx_hash", display(pending.tx_hash()));
+
+        Ok(pending.get_receipt().await?)
+    }
+}
+
 #[async_trait]
 impl Arbitrageur for FixedArbitrageur {
     async fn init(&mut self, _signal: &Signal, _provider: AnvilProvider) {}
 
-    async fn arbitrage(&mut self, signal: &Signal, provider: AnvilProvider) {
-        let controller = ArenaController::new(signal.controller, provider.clone());
+    async fn arbitrage(
+        &mut self,
+        signal: &Signal,
+        provider: AnvilProvider,
+    ) -> Option<ArbitrageRecord> {
+        // the controller compares prices exactly like this, and does nothing when they are equal
+        let sqrt_price = U512::from(signal.sqrt_price_x96);
+        let pool_price = (sqrt_price * sqrt_price * U512::from(WAD)) >> 192;
+        let lex_price = U512::from(signal.lex_price);
 
-        controller
-            .equalizePrice(self.depth)
-            .nonce(
-                provider
-                    .clone()
-                    .get_transaction_count(provider.clone().default_signer_address())
-                    .await
-                    .unwrap(),
+        if pool_price == lex_price {
+            return None;
+        }
+
+        let receipt = match self.equalize(signal, provider).await {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                error!(error = %e, "failed to equalize price");
+                return None;
+            }
+        };
+
+        debug!(depth = %self.depth, "price equalized");
+
+        // a negative depth is an exact input and a positive one an exact output
+        let depth = self.depth.unsigned_abs();
+        let (amount_in, amount_out) = if self.depth.is_negative() {
+            (depth, U256::ZERO)
+        } else {
+            (U256::ZERO, depth)
+        };
+
+        Some(ArbitrageRecord {
+            step: signal.step,
+            arbitrageur: 0,
+            priority_fee: 0,
+            zero_for_one: pool_price > lex_price,
+            amount_in,
+            amount_out,
+            amount_returned: U256::ZERO,
+            profit: I256::ZERO,
+            gas_used: receipt.gas_used,
+            gas_cost: U256::ZERO,
+        })
+    }
+}
+
+/// Implementation of an [`Arbitrageur`] that uses the closed-form optimal swap amount to determine the optimal arbitrage.
+///
+/// Each step the pool's active liquidity is read and the exact input required to move the pool price to the edge of the fee-adjusted no-arbitrage band around the LEX price is swapped.
+/// Its record holds the closed-form output of the swap, valued at the LEX price.
+#[derive(Default)]
+pub struct OptimalArbitrageur;
+
+#[async_trait]
+impl Arbitrageur for OptimalArbitrageur {
+    async fn init(&mut self, _signal: &Signal, _provider: AnvilProvider) {}
+
+    async fn arbitrage(
+        &mut self,
+        signal: &Signal,
+        provider: AnvilProvider,
+    ) -> Option<ArbitrageRecord> {
+        let engine = Engine::new(signal.controller);
+
+        let swap = optimal_arbitrage(
+            U256::from(signal.sqrt_price_x96),
+            signal.lex_price,
+            signal.liquidity,
+            signal.lp_fee.to(),
+        )?;
+
+        debug!(
+            zero_for_one = swap.zero_for_one,
+            amount_in = %swap.amount_in,
+            "swapping to the no-arbitrage band"
+        );
+
+        let receipt = match engine
+            .swap(
+                swap.zero_for_one,
+                swap.amount_in,
+                Some(swap.sqrt_price_limit_x96.to()),
+                Bytes::new(),
+                provider,
             )
-            .send()
             .await
-            .unwrap()
-            .watch()
-            .await
-            .unwrap();
+        {
+            Ok(receipt) => receipt,
+            Err(e) => {
+                error!(error = %e, "arbitrage swap failed");
+                return None;
+            }
+        };
+
+        Some(ArbitrageRecord {
+            step: signal.step,
+            arbitrageur: 0,
+            priority_fee: 0,
+            zero_for_one: swap.zero_for_one,
+            amount_in: swap.amount_in,
+            amount_out: swap.amount_out,
+            amount_returned: U256::ZERO,
+            profit: profit(
+                swap.zero_for_one,
+                swap.amount_in,
+                lex_amount(swap.zero_for_one, swap.amount_out, signal.lex_price),
+                signal.lex_price,
+            )
+            .ok()?,
+            gas_used: receipt.gas_used,
+            gas_cost: U256::ZERO,
+        })
+    }
+}
+
+/// Implementation of an [`Arbitrageur`] that only trades when the round trip through the pool and the liquid exchange is profitable after gas.
+///
+/// Opportunities are sized with the closed-form optimal swap and priced against `gas_estimate * gas_price`. Profitable ones are executed as a pool swap followed by a `LiquidExchange.swap` back into the input token, and recorded in the ledger.
+pub struct FeeAwareArbitrageur {
+    /// Gas expected to be used by a round trip, used to price opportunities before trading.
+    pub gas_estimate: u128,
+
+    /// Price of a unit of gas in token1.
+    pub gas_price: u128,
+
+    /// Priority fee per unit of gas in token1, bid to act first and paid on top of the gas price.
+    pub priority_fee: u128,
+
+    /// Records of every arbitrage made.
+    pub ledger: Vec<ArbitrageRecord>,
+}
+
+impl FeeAwareArbitrageur {
+    /// Public constructor function for a new [`FeeAwareArbitrageur`].
+    pub fn new(gas_estimate: u128, gas_price: u128) -> Self {
+        Self {
+            gas_estimate,
+            gas_price,
+            priority_fee: 0,
+            ledger: Vec::new(),
+        }
+    }
+
+    /// Set the priority fee bid when competing with other arbitrageurs.
+    pub fn with_priority_fee(mut self, priority_fee: u128) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+
+    async fn execute(
+        &self,
+        engine: &Engine,
+        swap: &OptimalSwap,
+        signal: &Signal,
+        provider: AnvilProvider,
+    ) -> Result<ArbitrageRecord, ArenaError> {
+        let sender = provider.default_signer_address();
+
+        let select = |(balance0, balance1): (U256, U256)| {
+            if swap.zero_for_one {
+                (balance0, balance1)
+            } else {
+                (balance1, balance0)
+            }
+        };
+
+        let (in_before, out_before) = select(engine.balances(sender, provider.clone()).await?);
+
+        let pool = engine
+            .swap(
+                swap.zero_for_one,
+                swap.amount_in,
+                Some(swap.sqrt_price_limit_x96.to()),
+                Bytes::new(),
+                provider.clone(),
+            )
+            .await?;
+
+        // the swap stops at its price limit, so it may not spend everything that was minted for it
+        let (in_after_pool, out_after) = select(engine.balances(sender, provider.clone()).await?);
+        let amount_in = in_before + swap.amount_in - in_after_pool;
+        let amount_out = out_after - out_before;
+
+        let lex = engine
+            .swap_on_lex(!swap.zero_for_one, amount_out, provider.clone())
+            .await?;
+
+        let (in_after, _) = select(engine.balances(sender, provider).await?);
+        let amount_returned = in_after - in_after_pool;
+
+        let gas_used = pool.gas_used + lex.gas_used;
+
+        Ok(ArbitrageRecord {
+            step: signal.step,
+            arbitrageur: 0,
+            priority_fee: self.priority_fee,
+            zero_for_one: swap.zero_for_one,
+            amount_in,
+            amount_out,
+            amount_returned,
+            profit: profit(
+                swap.zero_for_one,
+                amount_in,
+                amount_returned,
+                signal.lex_price,
+            )?,
+            gas_used,
+            gas_cost: U256::from(gas_used) * U256::from(self.gas_price + self.priority_fee),
+        })
+    }
+}
+
+#[async_trait]
+impl Arbitrageur for FeeAwareArbitrageur {
+    async fn init(&mut self, _signal: &Signal, _provider: AnvilProvider) {}
+
+    async fn arbitrage(
+        &mut self,
+        signal: &Signal,
+        provider: AnvilProvider,
+    ) -> Option<ArbitrageRecord> {
+        let engine = Engine::new(signal.controller);
+
+        let swap = optimal_arbitrage(
+            U256::from(signal.sqrt_price_x96),
+            signal.lex_price,
+            signal.liquidity,
+            signal.lp_fee.to(),
+        )?;
+
+        let amount_returned = lex_amount(swap.zero_for_one, swap.amount_out, signal.lex_price);
+
+        let expected = profit(
+            swap.zero_for_one,
+            swap.amount_in,
+            amount_returned,
+            signal.lex_price,
+        )
+        .ok()?;
+        let cost = I256::try_from(
+            U256::from(self.gas_estimate) * U256::from(self.gas_price + self.priority_fee),
+        )
+        .ok()?;
+
+        if expected <= cost {
+            debug!(expected = %expected, cost = %cost, "opportunity does not cover gas");
+            return None;
+        }
+
+        let record = match self.execute(&engine, &swap, signal, provider).await {
+            Ok(record) => record,
+            Err(e) => {
+                error!(error = %e, "arbitrage failed");
+                return None;
+            }
+        };
+
+        debug!(
+            zero_for_one = record.zero_for_one,
+            amount_in = %record.amount_in,
+            profit = %record.profit,
+            gas_used = record.gas_used,
+            "arbitrage executed"
+        );
+
+        self.ledger.push(record.clone());
+
+        Some(record)
+    }
+
+    fn bid(&mut self, _signal: &Signal) -> u128 {
+        self.priority_fee
+    }
+}
+
+/// Amount of the input token of a pool swap that its output `amount_out` buys back on the LEX.
+fn lex_amount(zero_for_one: bool, amount_out: U256, lex_price: U256) -> U256 {
+    if zero_for_one {
+        amount_out * WAD / lex_price
+    } else {
+        amount_out * lex_price / WAD
+    }
+}
+
+/// Profit in token1 of selling `amount_in` to the pool and buying back `amount_returned` of the same token on the LEX.
+fn profit(
+    zero_for_one: bool,
+    amount_in: U256,
+    amount_returned: U256,
+    lex_price: U256,
+) -> Result<I256, ArenaError> {
+    let profit = I256::try_from(amount_returned)? - I256::try_from(amount_in)?;
+
+    if zero_for_one {
+        Ok(profit * I256::try_from(lex_price)? / I256::try_from(WAD)?)
+    } else {
+        Ok(profit)
+    }
+}
+
+/// Condition under which a [`ThrottledArbitrageur`] lets its inner arbitrageur trade.
+#[derive(Debug, Clone)]
+pub enum Throttle {
+    /// Arbitrage on each step with a fixed probability.
+    Probability(f64),
+
+    /// Arbitrage once the relative mispricing has exceeded `threshold` for `steps` consecutive steps.
+    Persistence {
+        /// Relative deviation of the pool price from the LEX price.
+        threshold: f64,
+
+        /// Number of consecutive steps the mispricing must persist for.
+        steps: usize,
+    },
+
+    /// Arbitrage `steps` steps after a relative mispricing above `threshold` is first observed.
+    Delay {
+        /// Relative deviation of the pool price from the LEX price.
+        threshold: f64,
+
+        /// Number of steps between observing the mispricing and acting on it.
+        steps: usize,
+    },
+}
+
+/// Wrapper around an [`Arbitrageur`] that models a less efficient market, in which arbitrage is probabilistic or latency-limited.
+pub struct ThrottledArbitrageur {
+    /// The arbitrageur that trades when the throttle allows it.
+    pub inner: Box<dyn Arbitrageur + Send>,
+
+    /// Condition under which the inner arbitrageur trades.
+    pub throttle: Throttle,
+
+    /// Number of consecutive steps the mispricing has exceeded the threshold for.
+    streak: usize,
+
+    /// Number of steps remaining until a delayed arbitrage is due.
+    countdown: Option<usize>,
+
+    rng: StdRng,
+}
+
+impl ThrottledArbitrageur {
+    /// Public constructor function for a new [`ThrottledArbitrageur`], seeding its random number generator.
+    pub fn new(inner: Box<dyn Arbitrageur + Send>, throttle: Throttle, seed: u64) -> Self {
+        Self {
+            inner,
+            throttle,
+            streak: 0,
+            countdown: None,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    /// Advance the throttle by one step and decide whether to arbitrage on it.
+    fn admit(&mut self, signal: &Signal) -> bool {
+        match self.throttle {
+            Throttle::Probability(probability) => self.rng.gen_bool(probability.clamp(0.0, 1.0)),
+            Throttle::Persistence { threshold, steps } => {
+                if signal.mispricing() > threshold {
+                    self.streak += 1;
+                } else {
+                    self.streak = 0;
+                }
+
+                self.streak >= steps.max(1)
+            }
+            Throttle::Delay { threshold, steps } => {
+                if self.countdown.is_none() && signal.mispricing() > threshold {
+                    self.countdown = Some(steps);
+                }
+
+                match self.countdown {
+                    Some(0) => {
+                        self.countdown = None;
+                        true
+                    }
+                    Some(remaining) => {
+                        self.countdown = Some(remaining - 1);
+                        false
+                    }
+                    None => false,
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Arbitrageur for ThrottledArbitrageur {
+    async fn init(&mut self, signal: &Signal, provider: AnvilProvider) {
+        self.inner.init(signal, provider).await;
+    }
+
+    async fn arbitrage(
+        &mut self,
+        signal: &Signal,
+        provider: AnvilProvider,
+    ) -> Option<ArbitrageRecord> {
+        if !self.admit(signal) {
+            trace!("arbitrage throttled");
+            return None;
+        }
+
+        self.inner.arbitrage(signal, provider).await
+    }
+
+    fn bid(&mut self, signal: &Signal) -> u128 {
+        self.inner.bid(signal)
     }
 }
 
@@ -51,5 +550,174 @@ pub struct EmptyArbitrageur;
 #[async_trait]
 impl Arbitrageur for EmptyArbitrageur {
     async fn init(&mut self, _signal: &Signal, _provider: AnvilProvider) {}
-    async fn arbitrage(&mut self, _signal: &Signal, _provider: AnvilProvider) {}
+    async fn arbitrage(
+        &mut self,
+        _signal: &Signal,
+        _provider: AnvilProvider,
+    ) -> Option<ArbitrageRecord> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        engine::math::price_to_sqrt_price_x96, types::controller::ArenaController::PoolKey,
+    };
+
+    /// A signal whose pool price deviates from a LEX price of one by `mispricing`.
+    fn signal(mispricing: f64) -> Signal {
+        let price = U256::from(((1.0 + mispricing) * 1e18) as u128);
+
+        Signal::new(
+            WAD,
+            None,
+            0.0,
+            Signed::ZERO,
+            price_to_sqrt_price_x96(price).to(),
+            0,
+            U256::ZERO,
+            U256::ZERO,
+            Uint::ZERO,
+            Uint::ZERO,
+            Address::ZERO,
+            PoolKey {
+                currency0: Address::ZERO,
+                currency1: Address::ZERO,
+                fee: Uint::ZERO,
+                tickSpacing: Signed::ZERO,
+                hooks: Address::ZERO,
+            },
+            Address::ZERO,
+            1.0,
+            Vec::new(),
+            Address::ZERO,
+        )
+    }
+
+    #[test]
+    fn test_competition_order() {
+        let bids = [5, 9, 5, 0, 9];
+        let mut rng = StdRng::seed_from_u64(0);
+
+        assert_eq!(
+            Competition::FirstCome.order(&bids, &mut rng),
+            vec![0, 1, 2, 3, 4]
+        );
+
+        // equal bids keep the order the arbitrageurs were added in
+        assert_eq!(
+            Competition::PriorityFeeAuction.order(&bids, &mut rng),
+            vec![1, 4, 0, 2, 3]
+        );
+        assert_eq!(
+            Competition::PriorityFeeAuction.order(&[0; 4], &mut rng),
+            vec![0, 1, 2, 3]
+        );
+
+        let mut shuffled = Competition::Random(3).order(&bids, &mut StdRng::seed_from_u64(3));
+        assert_eq!(
+            shuffled,
+            Competition::Random(3).order(&bids, &mut StdRng::seed_from_u64(3))
+        );
+
+        shuffled.sort();
+        assert_eq!(shuffled, vec![0, 1, 2, 3, 4]);
+    }
+
+    fn throttled(throttle: Throttle, seed: u64) -> ThrottledArbitrageur {
+        ThrottledArbitrageur::new(Box::new(EmptyArbitrageur), throttle, seed)
+    }
+
+    fn admissions(arbitrageur: &mut ThrottledArbitrageur, mispricings: &[f64]) -> Vec<bool> {
+        mispricings
+            .iter()
+            .map(|mispricing| arbitrageur.admit(&signal(*mispricing)))
+            .collect()
+    }
+
+    #[test]
+    fn test_probability_throttle() {
+        let mispricings = vec![0.0; 1000];
+
+        assert!(
+            !admissions(&mut throttled(Throttle::Probability(0.0), 0), &mispricings)
+                .contains(&true)
+        );
+        assert!(
+            !admissions(&mut throttled(Throttle::Probability(1.0), 0), &mispricings)
+                .contains(&false)
+        );
+
+        let first = admissions(&mut throttled(Throttle::Probability(0.3), 7), &mispricings);
+        let second = admissions(&mut throttled(Throttle::Probability(0.3), 7), &mispricings);
+
+        assert_eq!(first, second);
+
+        let admitted = first.iter().filter(|admitted| **admitted).count();
+        assert!((250..350).contains(&admitted));
+    }
+
+    #[test]
+    fn test_persistence_throttle() {
+        let mut arbitrageur = throttled(
+            Throttle::Persistence {
+                threshold: 0.01,
+                steps: 3,
+            },
+            0,
+        );
+
+        assert_eq!(
+            admissions(
+                &mut arbitrageur,
+                &[0.02, 0.02, 0.02, 0.02, 0.0, 0.02, 0.02, 0.02]
+            ),
+            vec![false, false, true, true, false, false, false, true]
+        );
+
+        let mut arbitrageur = throttled(
+            Throttle::Persistence {
+                threshold: 0.01,
+                steps: 0,
+            },
+            0,
+        );
+
+        assert_eq!(
+            admissions(&mut arbitrageur, &[0.0, 0.02]),
+            vec![false, true]
+        );
+    }
+
+    #[test]
+    fn test_delay_throttle() {
+        let mut arbitrageur = throttled(
+            Throttle::Delay {
+                threshold: 0.01,
+                steps: 2,
+            },
+            0,
+        );
+
+        // the mispricing observed on the first step is acted on two steps later, whatever happens in between
+        assert_eq!(
+            admissions(&mut arbitrageur, &[0.0, 0.02, 0.0, 0.02, 0.0, 0.0]),
+            vec![false, false, false, true, false, false]
+        );
+
+        let mut arbitrageur = throttled(
+            Throttle::Delay {
+                threshold: 0.01,
+                steps: 0,
+            },
+            0,
+        );
+
+        assert_eq!(
+            admissions(&mut arbitrageur, &[0.02, 0.0, 0.02]),
+            vec![true, false, true]
+        );
+    }
 }
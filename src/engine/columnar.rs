@@ -0,0 +1,246 @@
+use std::{fs::File, sync::Arc};
+
+use arrow::{
+    array::{ArrayRef, BooleanArray, Float64Array, Int64Array, StringArray, UInt64Array},
+    datatypes::{DataType, Field, Schema},
+    ipc::writer::FileWriter,
+    record_batch::RecordBatch,
+};
+use parquet::arrow::ArrowWriter;
+
+use crate::{
+    engine::{
+        inspector::{Inspector, LogMessage},
+        record::{Record, Value},
+    },
+    error::ArenaError,
+};
+
+/// File format of a columnar export.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnarFormat {
+    /// Apache Parquet.
+    Parquet,
+
+    /// Arrow IPC file format, also known as Feather v2.
+    ArrowIpc,
+}
+
+/// Implementation of an [`Inspector`] that writes [`Record`]s to a Parquet or Arrow IPC file with typed columns.
+///
+/// Records are stored in long format, one row per record, with the value in the column matching its type and the other value columns left null.
+/// Unsigned 256 bit values such as `sqrtPriceX96` are stored losslessly as decimal strings. The file is written in full on [`Inspector::save`].
+#[derive(Debug)]
+pub struct ColumnarWriter {
+    file_path: String,
+    format: ColumnarFormat,
+    records: Vec<Record>,
+}
+
+impl ColumnarWriter {
+    /// Public constructor function for a new [`ColumnarWriter`] for Parquet format.
+    pub fn new_parquet(file_path: String) -> Self {
+        Self {
+            file_path,
+            format: ColumnarFormat::Parquet,
+            records: Vec::new(),
+        }
+    }
+
+    /// Public constructor function for a new [`ColumnarWriter`] for Arrow IPC format.
+    pub fn new_arrow_ipc(file_path: String) -> Self {
+        Self {
+            file_path,
+            format: ColumnarFormat::ArrowIpc,
+            records: Vec::new(),
+        }
+    }
+
+    /// Write every record logged so far to the file, replacing its contents.
+    pub fn write(&self) -> Result<(), ArenaError> {
+        write_batch(records_batch(&self.records)?, &self.file_path, self.format)
+    }
+}
+
+impl Inspector<Record> for ColumnarWriter {
+    fn log(&mut self, record: Record) {
+        self.records.push(record);
+    }
+
+    fn log_scoped(&mut self, scope: &str, mut record: Record) {
+        record.strategy.get_or_insert_with(|| scope.to_string());
+        self.log(record);
+    }
+
+    fn inspect(&self, step: usize) -> Option<Record> {
+        self.records.get(step).cloned()
+    }
+
+    fn save(&mut self) -> Result<(), ArenaError> {
+        self.write()
+    }
+}
+
+/// Write [`LogMessage`]s, such as those loaded back from a [`Logger`](crate::engine::inspector::Logger) file, to a Parquet or Arrow IPC file.
+pub fn write_log_messages(
+    messages: &[LogMessage],
+    file_path: &str,
+    format: ColumnarFormat,
+) -> Result<(), ArenaError> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::UInt64, false),
+        Field::new("scope", DataType::Utf8, true),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("data", DataType::Utf8, false),
+    ]));
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter_values(
+            messages.iter().map(|message| message.id as u64),
+        )),
+        Arc::new(StringArray::from_iter(
+            messages.iter().map(|message| message.scope.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            messages.iter().map(|message| &message.name),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            messages.iter().map(|message| &message.data),
+        )),
+    ];
+
+    write_batch(RecordBatch::try_new(schema, columns)?, file_path, format)
+}
+
+fn records_batch(records: &[Record]) -> Result<RecordBatch, ArenaError> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("step", DataType::UInt64, true),
+        Field::new("time", DataType::Float64, false),
+        Field::new("strategy", DataType::Utf8, true),
+        Field::new("metric", DataType::Utf8, false),
+        Field::new("float", DataType::Float64, true),
+        Field::new("int", DataType::Int64, true),
+        Field::new("uint", DataType::Utf8, true),
+        Field::new("bool", DataType::Boolean, true),
+        Field::new("json", DataType::Utf8, true),
+    ]));
+
+    let value = |f: fn(&Value) -> Option<String>| {
+        StringArray::from_iter(records.iter().map(|record| f(&record.value)))
+    };
+
+    let columns: Vec<ArrayRef> = vec![
+        Arc::new(UInt64Array::from_iter(
+            records
+                .iter()
+                .map(|record| record.step.map(|step| step as u64)),
+        )),
+        Arc::new(Float64Array::from_iter_values(
+            records.iter().map(|record| record.time),
+        )),
+        Arc::new(StringArray::from_iter(
+            records.iter().map(|record| record.strategy.as_deref()),
+        )),
+        Arc::new(StringArray::from_iter_values(
+            records.iter().map(|record| &record.metric),
+        )),
+        Arc::new(Float64Array::from_iter(records.iter().map(
+            |record| match record.value {
+                Value::Float(value) => Some(value),
+                _ => None,
+            },
+        ))),
+        Arc::new(Int64Array::from_iter(records.iter().map(
+            |record| match record.value {
+                Value::Int(value) => Some(value),
+                _ => None,
+            },
+        ))),
+        Arc::new(value(|value| match value {
+            Value::Uint(value) => Some(value.to_string()),
+            _ => None,
+        })),
+        Arc::new(BooleanArray::from_iter(records.iter().map(
+            |record| match record.value {
+                Value::Bool(value) => Some(value),
+                _ => None,
+            },
+        ))),
+        Arc::new(value(|value| match value {
+            Value::Json(value) => Some(value.to_string()),
+            _ => None,
+        })),
+    ];
+
+    Ok(RecordBatch::try_new(schema, columns)?)
+}
+
+fn write_batch(
+    batch: RecordBatch,
+    file_path: &str,
+    format: ColumnarFormat,
+) -> Result<(), ArenaError> {
+    let file = File::create(file_path)?;
+
+    match format {
+        ColumnarFormat::Parquet => {
+            let mut writer = ArrowWriter::try_new(file, batch.schema(), None)?;
+            writer.write(&batch)?;
+            writer.close()?;
+        }
+        ColumnarFormat::ArrowIpc => {
+            let mut writer = FileWriter::try_new(file, &batch.schema())?;
+            writer.write(&batch)?;
+            writer.finish()?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::primitives::U256;
+    use arrow::array::Array;
+    use parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder;
+
+    use super::*;
+
+    #[test]
+    fn test_parquet_round_trip() {
+        let path = std::env::temp_dir().join("arena_test_parquet_round_trip.parquet");
+        let path = path.to_str().unwrap().to_string();
+
+        let sqrt_price_x96: U256 = U256::from(1) << 200;
+
+        let mut writer = ColumnarWriter::new_parquet(path.clone());
+        writer.log(Record::new(Some(0), 0.0, "price".to_string(), 1.5));
+        writer.log(Record::new(
+            Some(0),
+            0.0,
+            "sqrt_price_x96".to_string(),
+            sqrt_price_x96,
+        ));
+        writer.save().unwrap();
+
+        let batch = ParquetRecordBatchReaderBuilder::try_new(File::open(&path).unwrap())
+            .unwrap()
+            .build()
+            .unwrap()
+            .next()
+            .unwrap()
+            .unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let uint = batch
+            .column_by_name("uint")
+            .unwrap()
+            .as_any()
+            .downcast_ref::<StringArray>()
+            .unwrap();
+
+        assert_eq!(batch.num_rows(), 2);
+        assert!(uint.is_null(0));
+        assert_eq!(uint.value(1), sqrt_price_x96.to_string());
+    }
+}
@@ -0,0 +1,121 @@
+use super::*;
+
+/// Compiled [`ArenaController`], whose immutable references locate the addresses it holds within its runtime code.
+const ARTIFACT: &str = include_str!("../artifacts/ArenaController.json");
+
+/// Addresses of the contracts deployed by an [`ArenaController`].
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct Deployment {
+    /// The PoolManager.
+    pub(crate) manager: Address,
+
+    /// The Fetcher.
+    pub(crate) fetcher: Address,
+
+    /// The router that modifies liquidity on behalf of the controller.
+    pub(crate) liquidity_router: Address,
+
+    /// The router that swaps on behalf of participants.
+    pub(crate) swap_router: Address,
+
+    /// The liquid exchange.
+    pub(crate) liquid_exchange: Address,
+}
+
+impl Deployment {
+    /// Resolve the contracts deployed by a controller from the controller itself, rather than from the order it creates them in.
+    ///
+    /// The PoolManager and Fetcher are read from `constructSignal` and the liquidity router from `getRouter`.
+    /// The controller source exposes the swap router and the liquid exchange through its `swapRouter` and `lex` getters, but the compiled artifact predates them, so until it is rebuilt they are found among the addresses the controller holds as immutables, the liquid exchange being the one that quotes a `price`.
+    pub(crate) async fn resolve(
+        controller: Address,
+        provider: &AnvilProvider,
+    ) -> Result<Self, ArenaError> {
+        let instance = ArenaController::new(controller, provider.clone());
+        let signal = instance.constructSignal().call().await?._0;
+        let liquidity_router = instance.getRouter().call().await?._0;
+
+        let known = [
+            signal.manager,
+            signal.fetcher,
+            liquidity_router,
+            signal.pool.currency0,
+            signal.pool.currency1,
+        ];
+
+        let code = provider.get_code_at(controller).await?;
+
+        let mut candidates: Vec<Address> = immutable_offsets()?
+            .into_iter()
+            .filter_map(|start| code.get(start + 12..start + 32))
+            .map(Address::from_slice)
+            .filter(|address| !known.contains(address))
+            .collect();
+        candidates.sort();
+        candidates.dedup();
+
+        let mut liquid_exchanges = Vec::new();
+        let mut swap_routers = Vec::new();
+
+        for candidate in candidates {
+            match LiquidExchange::new(candidate, provider.clone())
+                .price()
+                .call()
+                .await
+            {
+                Ok(_) => liquid_exchanges.push(candidate),
+                Err(_) => swap_routers.push(candidate),
+            }
+        }
+
+        let (&[liquid_exchange], &[swap_router]) =
+            (liquid_exchanges.as_slice(), swap_routers.as_slice())
+        else {
+            return Err(ArenaError::DeploymentError(format!(
+                "expected one liquid exchange and one swap router, found {} and {}",
+                liquid_exchanges.len(),
+                swap_routers.len()
+            )));
+        };
+
+        Ok(Self {
+            manager: signal.manager,
+            fetcher: signal.fetcher,
+            liquidity_router,
+            swap_router,
+            liquid_exchange,
+        })
+    }
+}
+
+/// Offsets of the 32 byte words holding each immutable within the controller's runtime code.
+fn immutable_offsets() -> Result<Vec<usize>, ArenaError> {
+    let artifact: serde_json::Value = serde_json::from_str(ARTIFACT)?;
+
+    let references = artifact["deployedBytecode"]["immutableReferences"]
+        .as_object()
+        .ok_or_else(|| {
+            ArenaError::DeploymentError("artifact has no immutable references".to_string())
+        })?;
+
+    Ok(references
+        .values()
+        .filter_map(|locations| locations.get(0)?.get("start")?.as_u64())
+        .map(|start| start as usize)
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_immutable_offsets() {
+        let mut offsets = immutable_offsets().unwrap();
+        offsets.sort();
+        offsets.dedup();
+
+        // the PoolManager, both routers, the liquid exchange, the Fetcher and both currencies
+        assert_eq!(offsets.len(), 7);
+    }
+}
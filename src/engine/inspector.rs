@@ -1,17 +1,50 @@
-use std::{fs::OpenOptions, io::Seek};
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufRead, BufReader, BufWriter, Write},
+    sync::{Arc, Mutex},
+};
 
 use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    engine::{record::Record, statistics::Statistics},
+    error::ArenaError,
+};
 
 /// Trait allowing custom behavior to be defined for logging and inspecting values.
-pub trait Inspector<V> {
+pub trait Inspector<V>: Send {
     /// Log a value to state.
     fn log(&mut self, value: V);
 
+    /// Log a value on behalf of a participant, identified by `scope`. Ignores the scope by default.
+    fn log_scoped(&mut self, _scope: &str, value: V) {
+        self.log(value);
+    }
+
+    /// Log a [`Record`] reported by the runtime or a participant, such as an arbitrage, a block inclusion or liquidity provided just in time. Ignored by default.
+    fn log_record(&mut self, _record: Record) {}
+
+    /// Log a [`Record`] of the state of the market, such as the pool and LEX prices before and after arbitrage. Logged by the runtime on every step, whether or not it has a market recorder, and ignored by default.
+    fn log_market(&mut self, _record: Record) {}
+
     /// Inspect a value at a given time step.
     fn inspect(&self, step: usize) -> Option<V>;
 
-    /// Save the inspector state.
-    fn save(&self);
+    /// Inspect the value at a given time step among those logged on behalf of `scope`. None by default, for inspectors that don't keep the scope of their values.
+    fn inspect_scoped(&self, _scope: &str, _step: usize) -> Option<V> {
+        None
+    }
+
+    /// Statistics of a metric logged so far, if the inspector keeps them. None by default.
+    fn statistics(&self, _metric: &str) -> Option<Statistics> {
+        None
+    }
+
+    /// Save the inspector state, flushing anything buffered. Called by the runtime at the end of a simulation, which fails with the first error returned. Does nothing by default.
+    fn save(&mut self) -> Result<(), ArenaError> {
+        Ok(())
+    }
 }
 
 /// Type that allows for logging indexed values to files on disc.
@@ -20,6 +53,10 @@ pub struct LogMessage {
     /// Index of the log message.
     pub id: usize,
 
+    /// Participant that logged the message, if it was logged through a [`ScopedInspector`].
+    #[serde(default)]
+    pub scope: Option<String>,
+
     /// Key of the log message.
     pub name: String,
 
@@ -30,17 +67,25 @@ pub struct LogMessage {
 impl LogMessage {
     /// Public constructor function for a new [`LogMessage`].
     pub fn new(name: String, data: String) -> Self {
-        Self { id: 0, name, data }
+        Self {
+            id: 0,
+            scope: None,
+            name,
+            data,
+        }
     }
 }
 
 #[derive(Debug)]
-/// Custom implementation of an [`Inspector`] for logging values to a file (CSV or JSON).
+/// Custom implementation of an [`Inspector`] for logging values to a file (CSV, JSON or JSON Lines).
+///
+/// CSV and JSON Lines records are streamed through a buffered writer as they are logged, and flushed on [`Inspector::save`]. JSON files hold a single array, so they are written in full on save.
 pub struct Logger {
     values: Vec<LogMessage>,
     counter: usize,
     file_path: String,
     format: LogFormat,
+    writer: Option<BufWriter<File>>,
 }
 
 #[derive(Debug)]
@@ -48,56 +93,99 @@ pub struct Logger {
 enum LogFormat {
     Csv,
     Json,
+    JsonLines,
 }
 
 impl Logger {
     /// Public constructor function for a new [`Logger`] for CSV format.
     pub fn new_csv(file_path: String) -> Self {
-        Self {
-            values: Vec::new(),
-            counter: 0,
-            file_path,
-            format: LogFormat::Csv,
-        }
+        Self::new(file_path, LogFormat::Csv)
     }
 
     /// Public constructor function for a new [`Logger`] for JSON format.
     pub fn new_json(file_path: String) -> Self {
+        Self::new(file_path, LogFormat::Json)
+    }
+
+    /// Public constructor function for a new [`Logger`] for JSON Lines format, with one JSON object per line.
+    pub fn new_json_lines(file_path: String) -> Self {
+        Self::new(file_path, LogFormat::JsonLines)
+    }
+
+    fn new(file_path: String, format: LogFormat) -> Self {
         Self {
             values: Vec::new(),
             counter: 0,
             file_path,
-            format: LogFormat::Json,
+            format,
+            writer: None,
         }
     }
 
-    /// Append a log message to the appropriate file format.
-    fn append_to_file(&self, record: &LogMessage) -> Result<(), Box<dyn std::error::Error>> {
-        let mut file = OpenOptions::new()
-            .append(true)
-            .create(true)
-            .open(&self.file_path)?;
+    /// Load every log message from a JSON Lines file, in the order they were logged.
+    pub fn read_json_lines(file_path: &str) -> Result<Vec<LogMessage>, ArenaError> {
+        let reader = BufReader::new(File::open(file_path)?);
+        let mut messages = Vec::new();
+
+        for line in reader.lines() {
+            let line = line?;
+
+            if !line.trim().is_empty() {
+                messages.push(serde_json::from_str(&line)?);
+            }
+        }
+
+        Ok(messages)
+    }
 
+    /// Buffered writer appending to the log file, opened on first use.
+    fn writer(&mut self) -> std::io::Result<&mut BufWriter<File>> {
+        if self.writer.is_none() {
+            let file = OpenOptions::new()
+                .append(true)
+                .create(true)
+                .open(&self.file_path)?;
+
+            self.writer = Some(BufWriter::new(file));
+        }
+
+        Ok(self.writer.as_mut().unwrap())
+    }
+
+    /// Append a log message to the appropriate file format.
+    fn append_to_file(&mut self, record: &LogMessage) -> Result<(), Box<dyn std::error::Error>> {
         match self.format {
             LogFormat::Csv => {
-                let mut writer = csv::Writer::from_writer(file);
-                writer.serialize((record.id, &record.name, &record.data))?;
-                writer.flush()?;
+                // the row is encoded on its own and appended to the buffered writer, since flushing a CSV writer also flushes the file
+                let mut row = csv::Writer::from_writer(Vec::new());
+                row.serialize((record.id, &record.scope, &record.name, &record.data))?;
+
+                self.writer()?.write_all(&row.into_inner()?)?;
             }
-            LogFormat::Json => {
-                let mut records: Vec<LogMessage> = if file.metadata()?.len() > 0 {
-                    serde_json::from_reader(&file)?
-                } else {
-                    Vec::new()
-                };
-                records.push(record.clone());
-                file.set_len(0)?;
-                file.seek(std::io::SeekFrom::Start(0))?;
-                serde_json::to_writer_pretty(file, &records)?;
+            LogFormat::Json => {}
+            LogFormat::JsonLines => {
+                let writer = self.writer()?;
+                serde_json::to_writer(&mut *writer, record)?;
+                writer.write_all(b"\n")?;
             }
         }
         Ok(())
     }
+
+    /// Write out everything logged so far.
+    fn flush(&mut self) -> Result<(), ArenaError> {
+        if let LogFormat::Json = self.format {
+            let mut writer = BufWriter::new(File::create(&self.file_path)?);
+            serde_json::to_writer_pretty(&mut writer, &self.values)?;
+            writer.flush()?;
+        }
+
+        if let Some(writer) = self.writer.as_mut() {
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Inspector<LogMessage> for Logger {
@@ -107,15 +195,173 @@ impl Inspector<LogMessage> for Logger {
         self.values.push(value.clone());
 
         if let Err(e) = self.append_to_file(&value) {
-            eprintln!("Failed to append to file: {}", e);
+            error!(error = %e, file = %self.file_path, "failed to append to log file");
         }
     }
 
+    fn log_scoped(&mut self, scope: &str, mut value: LogMessage) {
+        value.scope = Some(scope.to_string());
+        self.log(value);
+    }
+
+    fn log_record(&mut self, record: Record) {
+        self.log(record.into());
+    }
+
     fn inspect(&self, step: usize) -> Option<LogMessage> {
         self.values.get(step).cloned()
     }
 
-    fn save(&self) {}
+    fn inspect_scoped(&self, scope: &str, step: usize) -> Option<LogMessage> {
+        self.values
+            .iter()
+            .filter(|value| value.scope.as_deref() == Some(scope))
+            .nth(step)
+            .cloned()
+    }
+
+    fn save(&mut self) -> Result<(), ArenaError> {
+        self.flush()
+    }
+}
+
+/// Implementation of an [`Inspector`] that forwards everything it is given to several inspectors, such as a file logger alongside an in-memory collector.
+///
+/// Inspecting a step returns the value of the first inspector that has one.
+pub struct FanoutInspector<V> {
+    /// The inspectors that every value is forwarded to.
+    pub inspectors: Vec<Box<dyn Inspector<V>>>,
+}
+
+impl<V> FanoutInspector<V> {
+    /// Public constructor function for a new [`FanoutInspector`].
+    pub fn new(inspectors: Vec<Box<dyn Inspector<V>>>) -> Self {
+        Self { inspectors }
+    }
+}
+
+impl<V: Clone> Inspector<V> for FanoutInspector<V> {
+    fn log(&mut self, value: V) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.log(value.clone());
+        }
+    }
+
+    fn log_scoped(&mut self, scope: &str, value: V) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.log_scoped(scope, value.clone());
+        }
+    }
+
+    fn log_record(&mut self, record: Record) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.log_record(record.clone());
+        }
+    }
+
+    fn log_market(&mut self, record: Record) {
+        for inspector in self.inspectors.iter_mut() {
+            inspector.log_market(record.clone());
+        }
+    }
+
+    fn inspect(&self, step: usize) -> Option<V> {
+        self.inspectors
+            .iter()
+            .find_map(|inspector| inspector.inspect(step))
+    }
+
+    fn inspect_scoped(&self, scope: &str, step: usize) -> Option<V> {
+        self.inspectors
+            .iter()
+            .find_map(|inspector| inspector.inspect_scoped(scope, step))
+    }
+
+    fn statistics(&self, metric: &str) -> Option<Statistics> {
+        self.inspectors
+            .iter()
+            .find_map(|inspector| inspector.statistics(metric))
+    }
+
+    fn save(&mut self) -> Result<(), ArenaError> {
+        // every inspector is saved, even once one has failed
+        self.inspectors
+            .iter_mut()
+            .map(|inspector| inspector.save())
+            .fold(Ok(()), Result::and)
+    }
+}
+
+/// Handle to a shared [`Inspector`] that tags every value logged through it with a participant's scope.
+///
+/// The [`Arena`](crate::Arena) hands each strategy its own handle, so that the logs of several strategies sharing an inspector can be told apart.
+/// Inspecting a handle only sees the values logged through it, provided the shared inspector keeps their scope.
+pub struct ScopedInspector<V> {
+    scope: String,
+    inner: Arc<Mutex<Box<dyn Inspector<V>>>>,
+}
+
+impl<V> ScopedInspector<V> {
+    /// Public constructor function for a new [`ScopedInspector`].
+    pub fn new(scope: String, inner: Arc<Mutex<Box<dyn Inspector<V>>>>) -> Self {
+        Self { scope, inner }
+    }
+
+    /// The scope values logged through this handle are tagged with.
+    pub fn scope(&self) -> &str {
+        &self.scope
+    }
+
+    /// Create a handle to the same inspector for a nested scope, such as a component of a strategy.
+    pub fn scoped(&self, scope: &str) -> Self {
+        Self::new(format!("{}/{}", self.scope, scope), self.inner.clone())
+    }
+}
+
+impl<V> Inspector<V> for ScopedInspector<V> {
+    fn log(&mut self, value: V) {
+        self.inner.lock().unwrap().log_scoped(&self.scope, value);
+    }
+
+    fn log_scoped(&mut self, scope: &str, value: V) {
+        self.inner
+            .lock()
+            .unwrap()
+            .log_scoped(&format!("{}/{}", self.scope, scope), value);
+    }
+
+    fn log_record(&mut self, mut record: Record) {
+        record.strategy.get_or_insert_with(|| self.scope.clone());
+        self.inner.lock().unwrap().log_record(record);
+    }
+
+    fn log_market(&mut self, record: Record) {
+        self.inner.lock().unwrap().log_market(record);
+    }
+
+    /// Inspect the value at a given time step among those logged through this handle.
+    fn inspect(&self, step: usize) -> Option<V> {
+        self.inner.lock().unwrap().inspect_scoped(&self.scope, step)
+    }
+
+    fn inspect_scoped(&self, scope: &str, step: usize) -> Option<V> {
+        self.inner
+            .lock()
+            .unwrap()
+            .inspect_scoped(&format!("{}/{}", self.scope, scope), step)
+    }
+
+    /// Statistics of a metric logged through this handle.
+    fn statistics(&self, metric: &str) -> Option<Statistics> {
+        self.inner
+            .lock()
+            .unwrap()
+            .statistics(&format!("{}/{}", self.scope, metric))
+    }
+
+    fn save(&mut self) -> Result<(), ArenaError> {
+        self.inner.lock().unwrap().save()
+    }
 }
 
 /// No-op implementation of an [`Inspector`] for custom use cases.
@@ -126,5 +372,73 @@ impl Inspector<f64> for EmptyInspector {
         None
     }
     fn log(&mut self, _value: f64) {}
-    fn save(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_lines_round_trip() {
+        let path = std::env::temp_dir().join("arena_test_json_lines_round_trip.jsonl");
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let mut logger = Logger::new_json_lines(path.clone());
+
+        for i in 0..3 {
+            logger.log(LogMessage::new("price".to_string(), i.to_string()));
+        }
+
+        logger.save().unwrap();
+
+        let messages = Logger::read_json_lines(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(messages.len(), 3);
+        assert_eq!(messages[2].id, 2);
+        assert_eq!(messages[2].data, "2");
+    }
+
+    #[test]
+    fn test_csv_is_buffered_until_save() {
+        let path = std::env::temp_dir().join("arena_test_csv_is_buffered_until_save.csv");
+        let path = path.to_str().unwrap().to_string();
+        let _ = std::fs::remove_file(&path);
+
+        let mut logger = Logger::new_csv(path.clone());
+
+        logger.log(LogMessage::new("price".to_string(), "1.0".to_string()));
+        logger.log(LogMessage::new("price".to_string(), "2.0".to_string()));
+
+        assert!(std::fs::read_to_string(&path).unwrap().is_empty());
+
+        logger.save().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(contents, "0,,price,1.0\n1,,price,2.0\n");
+    }
+
+    #[test]
+    fn test_scoped_inspector_tags_fanout() {
+        let fanout: Box<dyn Inspector<LogMessage>> = Box::new(FanoutInspector::new(vec![
+            Box::new(Logger::new_json(String::new())),
+            Box::new(Logger::new_json(String::new())),
+        ]));
+
+        let mut scoped = ScopedInspector::new("lp".to_string(), Arc::new(Mutex::new(fanout)));
+        scoped.log(LogMessage::new("price".to_string(), "1.0".to_string()));
+        scoped
+            .scoped("hedge")
+            .log(LogMessage::new("price".to_string(), "2.0".to_string()));
+
+        assert_eq!(scoped.inspect(0).unwrap().scope.as_deref(), Some("lp"));
+        assert!(scoped.inspect(1).is_none());
+
+        let hedge = scoped.scoped("hedge").inspect(0).unwrap();
+        assert_eq!(hedge.scope.as_deref(), Some("lp/hedge"));
+        assert_eq!(hedge.data, "2.0");
+    }
 }
@@ -0,0 +1,326 @@
+use alloy::primitives::{U160, U256, U512};
+
+/// Minimum `sqrtPriceX96` of a Uniswap v4 pool, corresponding to the minimum tick.
+pub const MIN_SQRT_PRICE: U160 = U160::from_limbs([4295128739, 0, 0]);
+
+/// Maximum `sqrtPriceX96` of a Uniswap v4 pool, corresponding to the maximum tick.
+pub const MAX_SQRT_PRICE: U160 =
+    U160::from_limbs([6743328256752651558, 17280870778742802505, 4294805859]);
+
+/// Minimum tick of a Uniswap v4 pool.
+pub const MIN_TICK: i32 = -887272;
+
+/// Maximum tick of a Uniswap v4 pool.
+pub const MAX_TICK: i32 = 887272;
+
+/// Lowest and highest ticks usable by positions in a pool with the given tick spacing, which are the extreme ticks rounded inwards to a multiple of the spacing.
+pub fn usable_tick_range(tick_spacing: i32) -> (i32, i32) {
+    let spacing = tick_spacing.max(1);
+
+    (
+        -(MIN_TICK.abs() / spacing) * spacing,
+        (MAX_TICK / spacing) * spacing,
+    )
+}
+
+/// Denominator of Uniswap v4 fees, which are expressed in hundredths of a basis point.
+pub const FEE_DENOMINATOR: u32 = 1_000_000;
+
+/// One in 18 decimal fixed point, the scale of LEX prices.
+pub const WAD: U256 = U256::from_limbs([1_000_000_000_000_000_000, 0, 0, 0]);
+
+/// Convert an 18 decimal fixed point price of token0 in terms of token1 into a `sqrtPriceX96`.
+pub fn price_to_sqrt_price_x96(price: U256) -> U256 {
+    let scaled: U512 = (U512::from(price) << 192) / U512::from(WAD);
+
+    scaled.root(2).to()
+}
+
+/// Convert a `sqrtPriceX96` into the price of token0 in terms of token1.
+pub fn sqrt_price_x96_to_price(sqrt_price_x96: U256) -> f64 {
+    let sqrt_price: f64 = sqrt_price_x96.into();
+
+    (sqrt_price / 2f64.powi(96)).powi(2)
+}
+
+/// Compute the `sqrtPriceX96` at a tick, rounding up exactly like `TickMath.getSqrtPriceAtTick`.
+///
+/// # Panics
+/// Panics if the tick is outside of [`MIN_TICK`] and [`MAX_TICK`].
+pub fn sqrt_price_at_tick(tick: i32) -> U256 {
+    const RATIOS: [u128; 19] = [
+        0xfff97272373d413259a46990580e213a,
+        0xfff2e50f5f656932ef12357cf3c7fdcc,
+        0xffe5caca7e10e4e61c3624eaa0941cd0,
+        0xffcb9843d60f6159c9db58835c926644,
+        0xff973b41fa98c081472e6896dfb254c0,
+        0xff2ea16466c96a3843ec78b326b52861,
+        0xfe5dee046a99a2a811c461f1969c3053,
+        0xfcbe86c7900a88aedcffc83b479aa3a4,
+        0xf987a7253ac413176f2b074cf7815e54,
+        0xf3392b0822b70005940c7a398e4b70f3,
+        0xe7159475a2c29b7443b29c7fa6e889d9,
+        0xd097f3bdfd2022b8845ad8f792aa5825,
+        0xa9f746462d870fdf8a65dc1f90e061e5,
+        0x70d869a156d2a1b890bb3df62baf32f7,
+        0x31be135f97d08fd981231505542fcfa6,
+        0x9aa508b5b7a84e1c677de54f3e99bc9,
+        0x5d6af8dedb81196699c329225ee604,
+        0x2216e584f5fa1ea926041bedfe98,
+        0x48a170391f7dc42444e8fa2,
+    ];
+
+    assert!((MIN_TICK..=MAX_TICK).contains(&tick), "tick out of range");
+
+    let abs_tick = tick.unsigned_abs();
+
+    let mut ratio = if abs_tick & 1 != 0 {
+        U256::from(0xfffcb933bd6fad37aa2d162d1a594001_u128)
+    } else {
+        U256::from(1) << 128
+    };
+
+    for (bit, multiplier) in RATIOS.iter().enumerate() {
+        if abs_tick & (2 << bit) != 0 {
+            ratio = (ratio * U256::from(*multiplier)) >> 128;
+        }
+    }
+
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    let remainder: U256 = ratio % (U256::from(1) << 32);
+
+    (ratio >> 32) + U256::from(!remainder.is_zero() as u8)
+}
+
+/// Amount of token0 required to move the price between two `sqrtPriceX96` values at a given liquidity.
+pub fn amount0_delta(
+    sqrt_price_a: U256,
+    sqrt_price_b: U256,
+    liquidity: u128,
+    round_up: bool,
+) -> U256 {
+    let (lower, upper) = sorted(sqrt_price_a, sqrt_price_b);
+
+    if lower.is_zero() {
+        return U256::ZERO;
+    }
+
+    let numerator = (U512::from(liquidity) << 96) * U512::from(upper - lower);
+    let denominator = U512::from(upper) * U512::from(lower);
+
+    divide(numerator, denominator, round_up).to()
+}
+
+/// Amount of token1 required to move the price between two `sqrtPriceX96` values at a given liquidity.
+pub fn amount1_delta(
+    sqrt_price_a: U256,
+    sqrt_price_b: U256,
+    liquidity: u128,
+    round_up: bool,
+) -> U256 {
+    let (lower, upper) = sorted(sqrt_price_a, sqrt_price_b);
+
+    let numerator = U512::from(liquidity) * U512::from(upper - lower);
+
+    divide(numerator, U512::from(1) << 96, round_up).to()
+}
+
+/// Compute the output amount and resulting `sqrtPriceX96` of an exact-input swap, including the pool fee.
+///
+/// Like [`optimal_arbitrage`], this assumes the liquidity is constant over the move, i.e. that the swap does not cross an initialized tick.
+/// Returns `None` if the pool has no active liquidity, or if the swap would move the price beyond [`MIN_SQRT_PRICE`] or [`MAX_SQRT_PRICE`].
+pub fn swap_exact_input(
+    sqrt_price_x96: U256,
+    liquidity: u128,
+    fee: u32,
+    zero_for_one: bool,
+    amount_in: U256,
+) -> Option<(U256, U256)> {
+    if liquidity == 0 || fee >= FEE_DENOMINATOR {
+        return None;
+    }
+
+    // widened, since neither the fee nor the Q96 shift of an input near the top of the range fit in 256 bits
+    let amount_in =
+        U512::from(amount_in) * U512::from(FEE_DENOMINATOR - fee) / U512::from(FEE_DENOMINATOR);
+
+    let next: U512 = if zero_for_one {
+        let numerator: U512 = (U512::from(liquidity) << 96) * U512::from(sqrt_price_x96);
+        let denominator: U512 =
+            (U512::from(liquidity) << 96) + amount_in * U512::from(sqrt_price_x96);
+
+        numerator.div_ceil(denominator)
+    } else {
+        U512::from(sqrt_price_x96) + (amount_in << 96) / U512::from(liquidity)
+    };
+
+    if next < U512::from(MIN_SQRT_PRICE) || next > U512::from(MAX_SQRT_PRICE) {
+        return None;
+    }
+
+    let next: U256 = next.to();
+
+    if zero_for_one {
+        Some((amount1_delta(next, sqrt_price_x96, liquidity, false), next))
+    } else {
+        Some((amount0_delta(sqrt_price_x96, next, liquidity, false), next))
+    }
+}
+
+/// An exact-input swap that moves the pool price to a target `sqrtPriceX96`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimalSwap {
+    /// Whether token0 is sold for token1.
+    pub zero_for_one: bool,
+
+    /// Amount of the input token, including the pool fee.
+    pub amount_in: U256,
+
+    /// Amount of the output token received.
+    pub amount_out: U256,
+
+    /// The `sqrtPriceX96` the pool is moved to, used as the swap's price limit.
+    pub sqrt_price_limit_x96: U256,
+}
+
+/// Compute the swap that moves the pool price to the edge of the fee-adjusted no-arbitrage band around `lex_price`.
+///
+/// Selling token0 to the pool is profitable while `p * (1 - fee) > lex_price`, and buying it while `p < lex_price * (1 - fee)`, so the pool is moved to `lex_price / (1 - fee)` or `lex_price * (1 - fee)` respectively.
+/// Returns `None` if the pool price already lies inside the band or the pool has no active liquidity.
+/// The amounts assume the liquidity is constant over the move; the price limit makes the swap stop at the target regardless.
+pub fn optimal_arbitrage(
+    sqrt_price_x96: U256,
+    lex_price: U256,
+    liquidity: u128,
+    fee: u32,
+) -> Option<OptimalSwap> {
+    if liquidity == 0 || fee >= FEE_DENOMINATOR {
+        return None;
+    }
+
+    let denominator = U256::from(FEE_DENOMINATOR);
+    let complement = U256::from(FEE_DENOMINATOR - fee);
+
+    let upper = price_to_sqrt_price_x96(lex_price * denominator / complement)
+        .min(U256::from(MAX_SQRT_PRICE) - U256::from(1));
+    let lower = price_to_sqrt_price_x96(lex_price * complement / denominator)
+        .max(U256::from(MIN_SQRT_PRICE) + U256::from(1));
+
+    let (zero_for_one, target) = if sqrt_price_x96 > upper {
+        (true, upper)
+    } else if sqrt_price_x96 < lower {
+        (false, lower)
+    } else {
+        return None;
+    };
+
+    let (amount_in, amount_out) = if zero_for_one {
+        (
+            amount0_delta(target, sqrt_price_x96, liquidity, true),
+            amount1_delta(target, sqrt_price_x96, liquidity, false),
+        )
+    } else {
+        (
+            amount1_delta(sqrt_price_x96, target, liquidity, true),
+            amount0_delta(sqrt_price_x96, target, liquidity, false),
+        )
+    };
+
+    if amount_in.is_zero() {
+        return None;
+    }
+
+    Some(OptimalSwap {
+        zero_for_one,
+        amount_in: (amount_in * denominator).div_ceil(complement),
+        amount_out,
+        sqrt_price_limit_x96: target,
+    })
+}
+
+fn sorted(a: U256, b: U256) -> (U256, U256) {
+    if a < b {
+        (a, b)
+    } else {
+        (b, a)
+    }
+}
+
+fn divide(numerator: U512, denominator: U512, round_up: bool) -> U512 {
+    if round_up {
+        numerator.div_ceil(denominator)
+    } else {
+        numerator / denominator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_optimal_arbitrage_moves_to_band_edge() {
+        let sqrt_price_x96 = U256::from(79228162514264337593543950336_u128);
+        let liquidity = 10_u128.pow(21);
+
+        let lex_price = U256::from(900_000_000_000_000_000_u128);
+        let swap = optimal_arbitrage(sqrt_price_x96, lex_price, liquidity, 3000).unwrap();
+
+        assert!(swap.zero_for_one);
+
+        let target = sqrt_price_x96_to_price(swap.sqrt_price_limit_x96);
+        assert!((target - 0.9 / 0.997).abs() < 1e-9);
+
+        assert!(optimal_arbitrage(sqrt_price_x96, WAD, liquidity, 3000).is_none());
+
+        let lex_price = U256::from(1_100_000_000_000_000_000_u128);
+        let swap = optimal_arbitrage(sqrt_price_x96, lex_price, liquidity, 0).unwrap();
+
+        assert!(!swap.zero_for_one);
+        assert!((sqrt_price_x96_to_price(swap.sqrt_price_limit_x96) - 1.1).abs() < 1e-9);
+
+        let (amount_out, next) =
+            swap_exact_input(sqrt_price_x96, liquidity, 0, false, swap.amount_in).unwrap();
+
+        assert!(swap.amount_out.abs_diff(amount_out) <= U256::from(1));
+        assert!((sqrt_price_x96_to_price(next) - 1.1).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_swap_exact_input_out_of_range() {
+        let sqrt_price_x96 = U256::from(79228162514264337593543950336_u128);
+
+        // inputs whose Q96 shift overflows 256 bits push the price off either end of the range
+        let amount_in = U256::MAX >> 8;
+        assert!(swap_exact_input(sqrt_price_x96, 1000, 3000, false, amount_in).is_none());
+        assert!(swap_exact_input(sqrt_price_x96, 1000, 3000, true, amount_in).is_none());
+
+        assert!(swap_exact_input(sqrt_price_x96, 1000, 3000, false, U256::from(1000)).is_some());
+    }
+
+    #[test]
+    fn test_usable_tick_range() {
+        assert_eq!(usable_tick_range(1), (MIN_TICK, MAX_TICK));
+        assert_eq!(usable_tick_range(60), (-887220, 887220));
+        assert_eq!(usable_tick_range(200), (-887200, 887200));
+    }
+
+    #[test]
+    fn test_sqrt_price_at_tick() {
+        assert_eq!(sqrt_price_at_tick(0), U256::from(1) << 96);
+        assert_eq!(sqrt_price_at_tick(MIN_TICK), U256::from(MIN_SQRT_PRICE));
+        assert_eq!(sqrt_price_at_tick(MAX_TICK), U256::from(MAX_SQRT_PRICE));
+
+        for tick in (0..20).map(|bit| 1 << bit).filter(|tick| *tick <= MAX_TICK) {
+            for tick in [tick, -tick] {
+                let price = sqrt_price_x96_to_price(sqrt_price_at_tick(tick));
+                let expected = 1.0001_f64.powi(tick);
+
+                assert!((price / expected - 1.0).abs() < 1e-9);
+            }
+        }
+    }
+}
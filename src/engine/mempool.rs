@@ -0,0 +1,319 @@
+use std::{
+    cmp::Reverse,
+    sync::{Arc, Mutex},
+};
+
+use alloy::primitives::{Address, Signed, I256, U256};
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// A state changing call on the pool that is queued in the [`Mempool`] rather than executed immediately.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Action {
+    /// An exact input swap. See [`Engine::swap`].
+    Swap {
+        /// Whether token0 is sold for token1.
+        zero_for_one: bool,
+
+        /// Amount of the input token.
+        amount_in: U256,
+
+        /// Price limit of the swap, or `None` for unlimited price impact.
+        sqrt_price_limit_x96: Option<Uint<160, 3>>,
+
+        /// Data passed to the pool's hooks.
+        hook_data: Bytes,
+    },
+
+    /// A change of liquidity. See [`Engine::modify_liquidity`].
+    ModifyLiquidity {
+        /// Amount of liquidity added, or removed if negative.
+        liquidity_delta: I256,
+
+        /// Lower tick of the position.
+        tick_lower: Signed<24, 1>,
+
+        /// Upper tick of the position.
+        tick_upper: Signed<24, 1>,
+
+        /// Data passed to the pool's hooks.
+        hook_data: Bytes,
+    },
+}
+
+/// An [`Action`] submitted by a market participant, waiting to be executed from its account.
+#[derive(Debug, Clone)]
+pub struct PendingTransaction {
+    /// Address of the account that submitted the transaction.
+    pub sender: Address,
+
+    /// The call the transaction performs.
+    pub action: Action,
+
+    /// Priority fee per unit of gas bid by the sender, used by [`PriorityFeeOrdering`].
+    pub priority_fee: u128,
+
+    pub(crate) provider: AnvilProvider,
+}
+
+impl PendingTransaction {
+    /// Public constructor function for a new [`PendingTransaction`], sent from the provider's default signer.
+    pub fn new(action: Action, provider: AnvilProvider) -> Self {
+        Self {
+            sender: provider.default_signer_address(),
+            action,
+            priority_fee: 0,
+            provider,
+        }
+    }
+
+    /// Set the priority fee per unit of gas bid by the sender.
+    pub fn with_priority_fee(mut self, priority_fee: u128) -> Self {
+        self.priority_fee = priority_fee;
+        self
+    }
+}
+
+/// Transactions submitted during the current step, in the order they will be executed.
+///
+/// Agents submit transactions while acting, and may then inspect and reorder the queue while searching, before the [`Arena`] executes it.
+/// The mempool is shared between every clone of the [`Engine`] it belongs to.
+#[derive(Debug, Clone, Default)]
+pub struct Mempool {
+    transactions: Arc<Mutex<Vec<PendingTransaction>>>,
+}
+
+impl Mempool {
+    /// Snapshot of the pending transactions, in execution order.
+    pub fn pending(&self) -> Vec<PendingTransaction> {
+        self.transactions.lock().unwrap().clone()
+    }
+
+    /// Number of pending transactions.
+    pub fn len(&self) -> usize {
+        self.transactions.lock().unwrap().len()
+    }
+
+    /// Whether there are no pending transactions.
+    pub fn is_empty(&self) -> bool {
+        self.transactions.lock().unwrap().is_empty()
+    }
+
+    /// Append a transaction to the end of the queue.
+    pub fn push(&self, transaction: PendingTransaction) {
+        self.transactions.lock().unwrap().push(transaction);
+    }
+
+    /// Insert a transaction at `index`, shifting every later transaction back. Indices past the end append the transaction.
+    pub fn insert(&self, index: usize, transaction: PendingTransaction) {
+        let mut transactions = self.transactions.lock().unwrap();
+        let index = index.min(transactions.len());
+
+        transactions.insert(index, transaction);
+    }
+
+    /// Remove and return the transaction at `index`, if there is one.
+    pub fn remove(&self, index: usize) -> Option<PendingTransaction> {
+        let mut transactions = self.transactions.lock().unwrap();
+
+        (index < transactions.len()).then(|| transactions.remove(index))
+    }
+
+    /// Reorder the pending transactions in place according to an [`OrderingPolicy`].
+    pub(crate) fn order(&self, policy: &mut dyn OrderingPolicy) {
+        policy.order(&mut self.transactions.lock().unwrap());
+    }
+
+    /// Take every pending transaction out of the queue, in execution order.
+    pub(crate) fn drain(&self) -> Vec<PendingTransaction> {
+        std::mem::take(&mut *self.transactions.lock().unwrap())
+    }
+}
+
+/// Record of the inclusion of a single pending transaction in a block built by the [`Arena`].
+///
+/// Without block building, pending transactions are executed one by one and only those that fail are recorded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InclusionRecord {
+    /// Step of the simulation at which the block was built.
+    pub step: usize,
+
+    /// Position of the transaction in the order chosen by the [`OrderingPolicy`], or in submission order without block building.
+    pub position: usize,
+
+    /// Address of the account that submitted the transaction.
+    pub sender: Address,
+
+    /// The call the transaction performed.
+    pub action: Action,
+
+    /// Priority fee per unit of gas bid by the sender.
+    pub priority_fee: u128,
+
+    /// Whether the transaction was mined into the block.
+    pub included: bool,
+
+    /// Whether the transaction executed without reverting.
+    pub success: bool,
+
+    /// Index of the transaction within the block, if it was included.
+    pub transaction_index: Option<u64>,
+
+    /// Gas used by the transaction.
+    pub gas_used: u128,
+
+    /// Reason the transaction could not be sent or executed, if any.
+    pub error: Option<String>,
+}
+
+/// Defines the order in which the pending transactions of a step are mined into its block.
+///
+/// The policy is applied once every participant has submitted its transactions and before agents search the [`Mempool`], so it never separates the transactions a searcher places around another.
+pub trait OrderingPolicy: Send {
+    /// Reorder the pending transactions, which are given in submission order.
+    fn order(&mut self, transactions: &mut Vec<PendingTransaction>);
+}
+
+/// [`OrderingPolicy`] that mines transactions in the order they were submitted.
+#[derive(Debug, Clone, Default)]
+pub struct FifoOrdering;
+
+impl OrderingPolicy for FifoOrdering {
+    fn order(&mut self, _transactions: &mut Vec<PendingTransaction>) {}
+}
+
+/// [`OrderingPolicy`] that mines transactions in a uniformly random order.
+#[derive(Debug, Clone)]
+pub struct RandomOrdering {
+    rng: StdRng,
+}
+
+impl RandomOrdering {
+    /// Public constructor function for a new [`RandomOrdering`], seeding its random number generator.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl OrderingPolicy for RandomOrdering {
+    fn order(&mut self, transactions: &mut Vec<PendingTransaction>) {
+        transactions.shuffle(&mut self.rng);
+    }
+}
+
+/// [`OrderingPolicy`] that mines transactions by descending priority fee, keeping submission order between equal bids.
+#[derive(Debug, Clone, Default)]
+pub struct PriorityFeeOrdering;
+
+impl OrderingPolicy for PriorityFeeOrdering {
+    fn order(&mut self, transactions: &mut Vec<PendingTransaction>) {
+        transactions.sort_by_key(|transaction| Reverse(transaction.priority_fee));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        network::EthereumWallet, primitives::B256, providers::ProviderBuilder,
+        signers::local::PrivateKeySigner,
+    };
+
+    use super::*;
+
+    /// Provider for the account with the given private key byte. It is never connected to, so no node is needed.
+    fn provider(key: u8) -> AnvilProvider {
+        let signer = PrivateKeySigner::from_bytes(&B256::repeat_byte(key)).unwrap();
+
+        ProviderBuilder::new()
+            .with_recommended_fillers()
+            .wallet(EthereumWallet::from(signer))
+            .on_http("http://localhost:8545".parse().unwrap())
+    }
+
+    fn swap(key: u8, amount_in: u64, priority_fee: u128) -> PendingTransaction {
+        PendingTransaction::new(
+            Action::Swap {
+                zero_for_one: true,
+                amount_in: U256::from(amount_in),
+                sqrt_price_limit_x96: None,
+                hook_data: Bytes::new(),
+            },
+            provider(key),
+        )
+        .with_priority_fee(priority_fee)
+    }
+
+    fn amounts(transactions: &[PendingTransaction]) -> Vec<u64> {
+        transactions
+            .iter()
+            .map(|transaction| match transaction.action {
+                Action::Swap { amount_in, .. } => amount_in.to(),
+                Action::ModifyLiquidity { .. } => 0,
+            })
+            .collect()
+    }
+
+    fn queue() -> Vec<PendingTransaction> {
+        vec![swap(1, 1, 5), swap(2, 2, 0), swap(3, 3, 9), swap(4, 4, 5)]
+    }
+
+    #[test]
+    fn test_ordering_policies() {
+        let mut transactions = queue();
+        FifoOrdering.order(&mut transactions);
+        assert_eq!(amounts(&transactions), vec![1, 2, 3, 4]);
+
+        // equal bids keep submission order
+        let mut transactions = queue();
+        PriorityFeeOrdering.order(&mut transactions);
+        assert_eq!(amounts(&transactions), vec![3, 1, 4, 2]);
+
+        let mut first = queue();
+        RandomOrdering::new(11).order(&mut first);
+        let mut second = queue();
+        RandomOrdering::new(11).order(&mut second);
+
+        assert_eq!(amounts(&first), amounts(&second));
+
+        let mut shuffled = amounts(&first);
+        shuffled.sort();
+        assert_eq!(shuffled, vec![1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_sandwich_survives_ordering() {
+        let policies: Vec<Box<dyn OrderingPolicy>> = vec![
+            Box::new(FifoOrdering),
+            Box::new(RandomOrdering::new(3)),
+            Box::new(PriorityFeeOrdering),
+        ];
+
+        for mut policy in policies {
+            let mempool = Mempool::default();
+
+            for transaction in queue() {
+                mempool.push(transaction);
+            }
+
+            // the arena orders the queue before searchers see it
+            mempool.order(policy.as_mut());
+
+            // bracket the largest swap without bidding, as the SandwichSearcher does
+            let victim = amounts(&mempool.pending())
+                .iter()
+                .position(|amount| *amount == 4)
+                .unwrap();
+
+            mempool.insert(victim, swap(9, 100, 0));
+            mempool.insert(victim + 2, swap(9, 200, 0));
+
+            let block = amounts(&mempool.drain());
+
+            assert_eq!(block[victim..victim + 3], [100, 4, 200]);
+        }
+    }
+}
@@ -0,0 +1,285 @@
+use std::{
+    collections::{BTreeMap, VecDeque},
+    fmt::Write as _,
+    net::SocketAddr,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use alloy::primitives::U256;
+use tokio::{
+    io::{AsyncReadExt, AsyncWriteExt},
+    net::TcpListener,
+};
+
+use crate::{
+    engine::{
+        inspector::Inspector,
+        record::{Record, Value},
+    },
+    error::ArenaError,
+};
+
+/// Implementation of an [`Inspector`] that serves the latest value of every numeric metric logged to it over a local HTTP endpoint, in the Prometheus text format.
+///
+/// Exposes `arena_step`, `arena_steps_per_second`, measured over the last [`RATE_WINDOW`] steps, and an `arena_metric` gauge per metric, labelled with the metric name and the strategy that logged it.
+/// Added with [`ArenaBuilder::with_inspector`](crate::ArenaBuilder::with_inspector), it also serves the market state logged by the runtime on every step, such as `post_arbitrage/pool_price` and `post_arbitrage/lex_price`, and every numeric field of the `arbitrage`, `inclusion` and `liquidity` records, as `arbitrage/profit` for instance.
+/// The exporter is a cheap handle to shared state, so clones of it can also be given to [`ArenaBuilder::with_market_recorder`](crate::ArenaBuilder::with_market_recorder) or to several strategies.
+#[derive(Debug, Clone)]
+pub struct MetricsExporter {
+    state: Arc<Mutex<MetricsState>>,
+    address: SocketAddr,
+}
+
+/// Number of most recent steps that `arena_steps_per_second` is measured over.
+pub const RATE_WINDOW: usize = 32;
+
+#[derive(Debug, Default)]
+struct MetricsState {
+    step: Option<usize>,
+
+    /// The most recent steps and when they were first observed, oldest first.
+    steps: VecDeque<(usize, Instant)>,
+    steps_per_second: f64,
+    gauges: BTreeMap<(Option<String>, String), f64>,
+}
+
+impl MetricsExporter {
+    /// Bind the metrics endpoint to `address`, such as `127.0.0.1:9000`, and start serving it in the background.
+    ///
+    /// Must be called from within a Tokio runtime.
+    pub async fn bind(address: &str) -> Result<Self, ArenaError> {
+        let listener = TcpListener::bind(address).await?;
+        let address = listener.local_addr()?;
+
+        let state = Arc::new(Mutex::new(MetricsState::default()));
+        let served = state.clone();
+
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                let body = served.lock().unwrap().render();
+
+                tokio::spawn(async move {
+                    let mut request = [0; 1024];
+                    let _ = stream.read(&mut request).await;
+
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\nContent-Type: text/plain; version=0.0.4\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+
+                    let _ = stream.write_all(response.as_bytes()).await;
+                    let _ = stream.shutdown().await;
+                });
+            }
+        });
+
+        Ok(Self { state, address })
+    }
+
+    /// Address the metrics endpoint is served on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.address
+    }
+}
+
+impl MetricsState {
+    fn observe(&mut self, record: Record) {
+        if let Some(step) = record.step {
+            if self.step != Some(step) {
+                self.steps.push_back((step, Instant::now()));
+
+                if self.steps.len() > RATE_WINDOW {
+                    self.steps.pop_front();
+                }
+
+                if let (Some((first, start)), Some((last, end))) =
+                    (self.steps.front(), self.steps.back())
+                {
+                    let elapsed = end.duration_since(*start).as_secs_f64();
+
+                    if elapsed > 0.0 {
+                        self.steps_per_second = last.saturating_sub(*first) as f64 / elapsed;
+                    }
+                }
+
+                self.step = Some(step);
+            }
+        }
+
+        let value = match record.value {
+            Value::Float(value) => value,
+            Value::Int(value) => value as f64,
+            Value::Uint(value) => f64::from(value),
+            Value::Bool(value) => f64::from(u8::from(value)),
+            Value::Json(serde_json::Value::Object(fields)) => {
+                // structured records, such as arbitrages, are served one gauge per numeric field
+                for (field, value) in fields {
+                    if let Some(value) = numeric(&value) {
+                        self.gauges.insert(
+                            (
+                                record.strategy.clone(),
+                                format!("{}/{}", record.metric, field),
+                            ),
+                            value,
+                        );
+                    }
+                }
+
+                return;
+            }
+            Value::Json(value) => match numeric(&value) {
+                Some(value) => value,
+                None => return,
+            },
+        };
+
+        self.gauges.insert((record.strategy, record.metric), value);
+    }
+
+    fn render(&self) -> String {
+        let mut body = String::new();
+
+        let _ = writeln!(body, "# TYPE arena_step gauge");
+        let _ = writeln!(
+            body,
+            "arena_step {}",
+            self.step.map(|step| step as f64).unwrap_or(f64::NAN)
+        );
+        let _ = writeln!(body, "# TYPE arena_steps_per_second gauge");
+        let _ = writeln!(body, "arena_steps_per_second {}", self.steps_per_second);
+        let _ = writeln!(body, "# TYPE arena_metric gauge");
+
+        for ((strategy, metric), value) in &self.gauges {
+            let _ = match strategy {
+                Some(strategy) => writeln!(
+                    body,
+                    "arena_metric{{strategy=\"{}\",metric=\"{}\"}} {}",
+                    escape(strategy),
+                    escape(metric),
+                    value
+                ),
+                None => writeln!(
+                    body,
+                    "arena_metric{{metric=\"{}\"}} {}",
+                    escape(metric),
+                    value
+                ),
+            };
+        }
+
+        body
+    }
+}
+
+/// Numeric value of a JSON field, including the strings that 256 bit integers are serialized as.
+fn numeric(value: &serde_json::Value) -> Option<f64> {
+    match value {
+        serde_json::Value::Number(number) => number.as_f64(),
+        serde_json::Value::Bool(value) => Some(f64::from(u8::from(*value))),
+        serde_json::Value::String(value) => match value.strip_prefix('-') {
+            Some(magnitude) => magnitude
+                .parse::<U256>()
+                .ok()
+                .map(|value| -f64::from(value)),
+            None => value.parse::<U256>().ok().map(f64::from),
+        },
+        _ => None,
+    }
+}
+
+/// Escape a Prometheus label value.
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+impl Inspector<Record> for MetricsExporter {
+    fn log(&mut self, record: Record) {
+        self.state.lock().unwrap().observe(record);
+    }
+
+    fn log_scoped(&mut self, scope: &str, mut record: Record) {
+        record.strategy.get_or_insert_with(|| scope.to_string());
+        self.log(record);
+    }
+
+    fn log_record(&mut self, record: Record) {
+        self.log(record);
+    }
+
+    fn log_market(&mut self, record: Record) {
+        self.log(record);
+    }
+
+    fn inspect(&self, _step: usize) -> Option<Record> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::net::TcpStream;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_metrics_exporter_serves_prometheus_text() {
+        let mut exporter = MetricsExporter::bind("127.0.0.1:0").await.unwrap();
+
+        exporter.log(Record::new(
+            Some(3),
+            0.3,
+            "post_arbitrage/pool_price".to_string(),
+            1.25,
+        ));
+        exporter.log_scoped("lp", Record::new(Some(3), 0.3, "value".to_string(), 42_i64));
+
+        let mut stream = TcpStream::connect(exporter.local_addr()).await.unwrap();
+        stream
+            .write_all(b"GET /metrics HTTP/1.1\r\n\r\n")
+            .await
+            .unwrap();
+
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+
+        assert!(response.starts_with("HTTP/1.1 200 OK"));
+        assert!(response.contains("arena_step 3\n"));
+        assert!(response.contains("arena_metric{metric=\"post_arbitrage/pool_price\"} 1.25\n"));
+        assert!(response.contains("arena_metric{strategy=\"lp\",metric=\"value\"} 42\n"));
+    }
+
+    #[test]
+    fn test_metrics_state_observes_records() {
+        let mut state = MetricsState::default();
+
+        let arbitrage = serde_json::json!({
+            "zero_for_one": true,
+            "amount_in": "0x4d2",
+            "profit": "-5",
+            "arbitrageur": 1,
+        });
+        state.observe(
+            Record::new(Some(0), 0.0, "arbitrage".to_string(), arbitrage)
+                .with_strategy("arbitrageur-1".to_string()),
+        );
+
+        let gauge =
+            |metric: &str| state.gauges[&(Some("arbitrageur-1".to_string()), metric.to_string())];
+        assert_eq!(gauge("arbitrage/amount_in"), 1234.0);
+        assert_eq!(gauge("arbitrage/profit"), -5.0);
+        assert_eq!(gauge("arbitrage/zero_for_one"), 1.0);
+
+        // only the most recent steps are kept to measure the step rate
+        for step in 1..=(RATE_WINDOW * 2) {
+            state.observe(Record::new(Some(step), 0.0, "value".to_string(), 1.0));
+        }
+
+        assert_eq!(state.steps.len(), RATE_WINDOW);
+        assert_eq!(state.steps.front().unwrap().0, RATE_WINDOW + 1);
+    }
+}
@@ -1,25 +1,177 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
 use alloy::{
-    primitives::{Address, Signed, I256},
+    network::TransactionBuilder,
+    primitives::{Address, Signed, B256, I256, U256},
     providers::{Provider, WalletProvider},
+    rpc::types::{BlockNumberOrTag, TransactionReceipt, TransactionRequest},
+};
+use tracing::{
+    debug, debug_span,
+    field::{display, Empty},
+    instrument, warn, Instrument, Span,
 };
 
 use super::*;
-use crate::{error::ArenaError, types::controller::ArenaController};
+use crate::{
+    error::ArenaError,
+    types::{
+        controller::ArenaController::{self, PoolKey},
+        exchange::LiquidExchange,
+        fetcher::Fetcher,
+        router::PoolSwapTest,
+        token::ArenaToken,
+    },
+};
 /// Defines a trait for custom arbitrage strategies.
 pub mod arbitrageur;
 
 /// Defines a trait that allows custom strategy logging and telemetry.
 pub mod inspector;
 
+/// Structured telemetry records and a schema-aware CSV writer for them.
+pub mod record;
+
+/// Online statistics of logged metrics.
+pub mod statistics;
+
+/// Parquet and Arrow IPC export of telemetry records.
+#[cfg(feature = "columnar")]
+pub mod columnar;
+
+/// Prometheus metrics endpoint for monitoring simulations live.
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+/// Fixed point math for Uniswap v4 prices and swap amounts.
+pub mod math;
+
+/// Storage layout of pools within the PoolManager.
+pub(crate) mod pool;
+
+/// Resolution of the contracts deployed by the controller.
+mod deployment;
+
+/// Defines the tick-level state and depth profile of a pool.
+pub mod ticks;
+
+/// Defines liquidity positions and their fee accounting.
+pub mod position;
+
+/// Defines the per-step queue of transactions submitted by market participants.
+pub mod mempool;
+
+use deployment::Deployment;
+use mempool::{Action, InclusionRecord, Mempool, PendingTransaction};
+use pool::PoolState;
+use position::Position;
+use record::Record;
+use ticks::{LiquidityBucket, TickInfo};
+
+/// Gas limit of each transaction mined by [`Engine::build_block`].
+const BLOCK_TRANSACTION_GAS_LIMIT: u128 = 1_000_000;
+
 /// Abstraction to allow strategies to call state changing functions on the PoolManager without having to worry about callbacks.
 #[derive(Debug, Clone)]
 pub struct Engine {
     pub(crate) controller: Address,
+    pub(crate) mempool: Mempool,
+    reports: Arc<Mutex<Vec<Record>>>,
+
+    /// Contracts deployed by the controller, resolved on first use and shared between clones.
+    deployment: Arc<Mutex<Option<Deployment>>>,
 }
 
 #[allow(clippy::redundant_closure)]
 impl Engine {
+    pub(crate) fn new(controller: Address) -> Self {
+        Self {
+            controller,
+            mempool: Mempool::default(),
+            reports: Arc::default(),
+            deployment: Arc::default(),
+        }
+    }
+
+    /// Addresses of the contracts deployed by the controller.
+    async fn deployment(&self, provider: &AnvilProvider) -> Result<Deployment, ArenaError> {
+        if let Some(deployment) = *self.deployment.lock().unwrap() {
+            return Ok(deployment);
+        }
+
+        let deployment = Deployment::resolve(self.controller, provider).await?;
+        *self.deployment.lock().unwrap() = Some(deployment);
+
+        Ok(deployment)
+    }
+
+    /// The queue of transactions submitted during the current step.
+    pub fn mempool(&self) -> &Mempool {
+        &self.mempool
+    }
+
+    /// Report a [`Record`] to the [`Arena`](crate::Arena)'s inspector, to be logged alongside the strategies' own records.
+    ///
+    /// Records reported by an agent without a strategy are attributed to it by the arena.
+    pub fn report(&self, record: Record) {
+        self.reports.lock().unwrap().push(record);
+    }
+
+    /// Take every record reported since the last call, in the order they were reported.
+    pub(crate) fn reports(&self) -> Vec<Record> {
+        std::mem::take(&mut *self.reports.lock().unwrap())
+    }
+
+    /// Queue an action in the [`Mempool`], to be executed from the provider's account once every participant has acted on this step.
+    pub fn submit(&self, action: Action, provider: AnvilProvider) {
+        self.mempool.push(PendingTransaction::new(action, provider));
+    }
+
+    /// Execute a pending transaction from its sender's account.
+    pub async fn execute(&self, transaction: PendingTransaction) -> Result<(), ArenaError> {
+        match transaction.action {
+            Action::Swap {
+                zero_for_one,
+                amount_in,
+                sqrt_price_limit_x96,
+                hook_data,
+            } => {
+                self.swap(
+                    zero_for_one,
+                    amount_in,
+                    sqrt_price_limit_x96,
+                    hook_data,
+                    transaction.provider,
+                )
+                .await?;
+            }
+            Action::ModifyLiquidity {
+                liquidity_delta,
+                tick_lower,
+                tick_upper,
+                hook_data,
+            } => {
+                self.modify_liquidity(
+                    liquidity_delta,
+                    tick_lower,
+                    tick_upper,
+                    hook_data,
+                    transaction.provider,
+                )
+                .await?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Modify pool liquidity.
+    ///
+    /// The transaction is mined immediately, even when the arena builds blocks; use [`Engine::submit`] to have it included in the step's block.
+    #[instrument(level = "debug", skip_all, fields(liquidity_delta = %liquidity_delta, tx_hash))]
     pub async fn modify_liquidity(
         &self,
         liquidity_delta: I256,
@@ -30,21 +182,616 @@ impl Engine {
     ) -> Result<(), ArenaError> {
         let controller = ArenaController::new(self.controller, provider.clone());
 
-        controller
+        let call = controller
             .addLiquidity(liquidity_delta, tick_lower, tick_upper, hook_data)
             .nonce(
                 provider
                     .get_transaction_count(provider.default_signer_address())
                     .await
                     .unwrap(),
-            )
-            .send()
-            .await
-            .map_err(ArenaError::ContractError)?
+            );
+
+        let pending = call.send().await.map_err(ArenaError::ContractError)?;
+
+        Span::current().record("tx_hash", display(pending.tx_hash()));
+
+        pending
             .watch()
             .await
             .map_err(|e| ArenaError::PendingTransactionError(e))?;
 
+        debug!("liquidity modified");
+
         Ok(())
     }
+
+    /// Swap an exact amount of one pool token for the other.
+    ///
+    /// The input tokens are minted to the caller and the swap is routed through the controller's swap router. If `sqrt_price_limit_x96` is `None` the swap has unlimited price impact.
+    /// Like [`Engine::modify_liquidity`], it is mined immediately rather than queued for the step's block.
+    #[instrument(level = "debug", skip_all, fields(zero_for_one, amount_in = %amount_in, tx_hash))]
+    pub async fn swap(
+        &self,
+        zero_for_one: bool,
+        amount_in: U256,
+        sqrt_price_limit_x96: Option<Uint<160, 3>>,
+        hook_data: Bytes,
+        provider: AnvilProvider,
+    ) -> Result<TransactionReceipt, ArenaError> {
+        let request = self
+            .prepare_swap(
+                zero_for_one,
+                amount_in,
+                sqrt_price_limit_x96,
+                hook_data,
+                &provider,
+            )
+            .await?;
+
+        let pending = provider
+            .send_transaction(request.with_nonce(nonce(&provider).await?))
+            .await?;
+
+        Span::current().record("tx_hash", display(pending.tx_hash()));
+
+        let receipt = pending.get_receipt().await?;
+
+        debug!(
+            gas_used = receipt.gas_used,
+            success = receipt.status(),
+            "swap mined"
+        );
+
+        Ok(receipt)
+    }
+
+    /// Swap an exact amount of one pool token for the other on the liquid exchange, at the current LEX price.
+    ///
+    /// The caller must hold the input tokens. The output tokens are minted to the exchange beforehand, so that it is infinitely liquid.
+    /// Like [`Engine::modify_liquidity`], it is mined immediately rather than queued for the step's block.
+    #[instrument(level = "debug", skip_all, fields(zero_for_one, amount_in = %amount_in, tx_hash))]
+    pub async fn swap_on_lex(
+        &self,
+        zero_for_one: bool,
+        amount_in: U256,
+        provider: AnvilProvider,
+    ) -> Result<TransactionReceipt, ArenaError> {
+        let key = self.pool_key(provider.clone()).await?;
+        let deployment = self.deployment(&provider).await?;
+        let exchange = LiquidExchange::new(deployment.liquid_exchange, provider.clone());
+
+        let price = exchange.price().call().await?._0;
+
+        let (token_in, token_out, amount_out) = if zero_for_one {
+            (key.currency0, key.currency1, amount_in * price / math::WAD)
+        } else {
+            (key.currency1, key.currency0, amount_in * math::WAD / price)
+        };
+
+        ArenaToken::new(token_out, provider.clone())
+            .mint(*exchange.address(), amount_out)
+            .nonce(nonce(&provider).await?)
+            .send()
+            .await?
+            .watch()
+            .await?;
+
+        approve(token_in, *exchange.address(), amount_in, &provider).await?;
+
+        let call = exchange
+            .swap(token_in, amount_in)
+            .nonce(nonce(&provider).await?);
+
+        let pending = call.send().await?;
+
+        Span::current().record("tx_hash", display(pending.tx_hash()));
+
+        let receipt = pending.get_receipt().await?;
+
+        debug!(
+            gas_used = receipt.gas_used,
+            success = receipt.status(),
+            "exchange swap mined"
+        );
+
+        Ok(receipt)
+    }
+
+    /// Fetch the token0 and token1 balances of an account.
+    pub async fn balances(
+        &self,
+        owner: Address,
+        provider: AnvilProvider,
+    ) -> Result<(U256, U256), ArenaError> {
+        let key = self.pool_key(provider.clone()).await?;
+
+        let balance0 = ArenaToken::new(key.currency0, provider.clone())
+            .balanceOf(owner)
+            .call()
+            .await?
+            ._0;
+        let balance1 = ArenaToken::new(key.currency1, provider)
+            .balanceOf(owner)
+            .call()
+            .await?
+            ._0;
+
+        Ok((balance0, balance1))
+    }
+
+    /// Fetch the active liquidity of the pool.
+    pub async fn liquidity(&self, provider: AnvilProvider) -> Result<u128, ArenaError> {
+        let key = self.pool_key(provider.clone()).await?;
+        let deployment = self.deployment(&provider).await?;
+
+        let slot = pool::state_slot(pool::pool_id(&key)) + U256::from(pool::LIQUIDITY_OFFSET);
+        let value = pool::read(deployment.manager, slot, 1, &provider).await?;
+
+        Ok(value[0].to::<u128>())
+    }
+
+    /// Address of the liquidity router, which owns every position added through [`Engine::modify_liquidity`] with a zero salt.
+    pub async fn liquidity_router(&self, provider: AnvilProvider) -> Result<Address, ArenaError> {
+        Ok(self.deployment(&provider).await?.liquidity_router)
+    }
+
+    /// Fetch a liquidity position of the pool, along with its value and uncollected fees at the current pool price.
+    ///
+    /// Positions added through [`Engine::modify_liquidity`] are owned by [`Engine::liquidity_router`] with a zero salt.
+    pub async fn position(
+        &self,
+        owner: Address,
+        tick_lower: i32,
+        tick_upper: i32,
+        salt: B256,
+        provider: AnvilProvider,
+    ) -> Result<Position, ArenaError> {
+        let key = self.pool_key(provider.clone()).await?;
+        let pool_id = pool::pool_id(&key);
+        let deployment = self.deployment(&provider).await?;
+
+        let state = PoolState::read(deployment.manager, pool_id, &provider).await?;
+        let current_tick = state.tick.as_i32();
+
+        // `Position.State` holds the liquidity followed by the last fee growth inside of each currency
+        let position_slot = pool::position_slot(pool_id, owner, tick_lower, tick_upper, salt);
+        let [liquidity, fee_growth_inside0_last_x128, fee_growth_inside1_last_x128] =
+            pool::read(deployment.manager, position_slot, 3, &provider).await?[..]
+        else {
+            return Err(ArenaError::StateError(
+                "expected 3 words of position state".to_string(),
+            ));
+        };
+        let liquidity = liquidity.to::<u128>();
+
+        let lower = self.tick_info(tick_lower, provider.clone()).await?;
+        let upper = self.tick_info(tick_upper, provider).await?;
+
+        let (fee_growth_inside0_x128, fee_growth_inside1_x128) = position::fee_growth_inside(
+            current_tick,
+            &lower,
+            &upper,
+            state.fee_growth_global0_x128,
+            state.fee_growth_global1_x128,
+        );
+
+        let (amount0, amount1) = position::amounts_for_liquidity(
+            current_tick,
+            U256::from(state.sqrt_price_x96),
+            tick_lower,
+            tick_upper,
+            liquidity,
+        );
+
+        Ok(Position {
+            owner,
+            tick_lower,
+            tick_upper,
+            salt,
+            liquidity,
+            fee_growth_inside0_last_x128,
+            fee_growth_inside1_last_x128,
+            amount0,
+            amount1,
+            fees0: position::fees_owed(
+                liquidity,
+                fee_growth_inside0_x128,
+                fee_growth_inside0_last_x128,
+            ),
+            fees1: position::fees_owed(
+                liquidity,
+                fee_growth_inside1_x128,
+                fee_growth_inside1_last_x128,
+            ),
+        })
+    }
+
+    /// Fetch the state of a single tick of the pool through `Fetcher.getTickInfo`.
+    pub async fn tick_info(
+        &self,
+        tick: i32,
+        provider: AnvilProvider,
+    ) -> Result<TickInfo, ArenaError> {
+        let key = self.pool_key(provider.clone()).await?;
+        let deployment = self.deployment(&provider).await?;
+        let fetcher = Fetcher::new(deployment.fetcher, provider);
+
+        let info = fetcher
+            .getTickInfo(
+                deployment.manager,
+                pool::pool_id(&key),
+                Signed::try_from(tick)?,
+            )
+            .call()
+            .await?;
+
+        Ok(TickInfo {
+            tick,
+            liquidity_gross: info.liquidityGross,
+            liquidity_net: info.liquidityNet,
+            fee_growth_outside0_x128: info.feeGrowthOutside0X128,
+            fee_growth_outside1_x128: info.feeGrowthOutside1X128,
+        })
+    }
+
+    /// Fetch every initialized tick of the pool between `tick_lower` and `tick_upper` inclusive, in ascending order.
+    ///
+    /// The pool's tick bitmap is walked one word at a time, so the number of calls grows with the width of the range divided by the tick spacing.
+    pub async fn ticks(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        provider: AnvilProvider,
+    ) -> Result<Vec<TickInfo>, ArenaError> {
+        let key = self.pool_key(provider.clone()).await?;
+        let pool_id = pool::pool_id(&key);
+        let manager = self.deployment(&provider).await?.manager;
+
+        let spacing = key.tickSpacing.as_i32();
+        let word = |tick: i32| (tick.div_euclid(spacing) >> 8) as i16;
+
+        let mut ticks = Vec::new();
+
+        for position in word(tick_lower)..=word(tick_upper) {
+            let bitmap = pool::read(
+                manager,
+                pool::tick_bitmap_slot(pool_id, position),
+                1,
+                &provider,
+            )
+            .await?[0];
+
+            for bit in (0..256).filter(|bit| bitmap.bit(*bit)) {
+                let tick = ((position as i32) * 256 + bit as i32) * spacing;
+
+                if (tick_lower..=tick_upper).contains(&tick) {
+                    ticks.push(self.tick_info(tick, provider.clone()).await?);
+                }
+            }
+        }
+
+        Ok(ticks)
+    }
+
+    /// Fetch the depth profile of the pool between `tick_lower` and `tick_upper`, as the active liquidity over each range between initialized ticks.
+    ///
+    /// See [`ticks::liquidity_distribution`].
+    pub async fn liquidity_distribution(
+        &self,
+        tick_lower: i32,
+        tick_upper: i32,
+        provider: AnvilProvider,
+    ) -> Result<Vec<LiquidityBucket>, ArenaError> {
+        let signal = ArenaController::new(self.controller, provider.clone())
+            .constructSignal()
+            .call()
+            .await?
+            ._0;
+        let current_tick = signal.currentTick.as_i32();
+
+        let ticks = self
+            .ticks(
+                tick_lower.min(current_tick),
+                tick_upper.max(current_tick),
+                provider.clone(),
+            )
+            .await?;
+        let active_liquidity = self.liquidity(provider).await?;
+
+        Ok(ticks::liquidity_distribution(
+            &ticks,
+            current_tick,
+            active_liquidity,
+            tick_lower,
+            tick_upper,
+        ))
+    }
+
+    /// Mint and approve the input tokens of a swap, returning the swap itself as an unsent transaction.
+    async fn prepare_swap(
+        &self,
+        zero_for_one: bool,
+        amount_in: U256,
+        sqrt_price_limit_x96: Option<Uint<160, 3>>,
+        hook_data: Bytes,
+        provider: &AnvilProvider,
+    ) -> Result<TransactionRequest, ArenaError> {
+        let key = self.pool_key(provider.clone()).await?;
+        let sender = provider.default_signer_address();
+        let router = self.deployment(provider).await?.swap_router;
+
+        let token_in = if zero_for_one {
+            key.currency0
+        } else {
+            key.currency1
+        };
+
+        ArenaToken::new(token_in, provider.clone())
+            .mint(sender, amount_in)
+            .nonce(nonce(provider).await?)
+            .send()
+            .await?
+            .watch()
+            .await?;
+
+        approve(token_in, router, amount_in, provider).await?;
+
+        let sqrt_price_limit_x96 = sqrt_price_limit_x96.unwrap_or(if zero_for_one {
+            math::MIN_SQRT_PRICE + Uint::from(1)
+        } else {
+            math::MAX_SQRT_PRICE - Uint::from(1)
+        });
+
+        Ok(PoolSwapTest::new(router, provider.clone())
+            .swap(
+                PoolSwapTest::PoolKey {
+                    currency0: key.currency0,
+                    currency1: key.currency1,
+                    fee: key.fee,
+                    tickSpacing: key.tickSpacing,
+                    hooks: key.hooks,
+                },
+                PoolSwapTest::SwapParams {
+                    zeroForOne: zero_for_one,
+                    amountSpecified: -I256::try_from(amount_in)?,
+                    sqrtPriceLimitX96: sqrt_price_limit_x96,
+                },
+                PoolSwapTest::TestSettings {
+                    takeClaims: false,
+                    settleUsingBurn: false,
+                },
+                hook_data,
+            )
+            .into_transaction_request())
+    }
+
+    /// Perform any setup a pending transaction needs, returning its call as an unsent transaction.
+    async fn prepare(
+        &self,
+        transaction: &PendingTransaction,
+    ) -> Result<TransactionRequest, ArenaError> {
+        match transaction.action.clone() {
+            Action::Swap {
+                zero_for_one,
+                amount_in,
+                sqrt_price_limit_x96,
+                hook_data,
+            } => {
+                self.prepare_swap(
+                    zero_for_one,
+                    amount_in,
+                    sqrt_price_limit_x96,
+                    hook_data,
+                    &transaction.provider,
+                )
+                .await
+            }
+            Action::ModifyLiquidity {
+                liquidity_delta,
+                tick_lower,
+                tick_upper,
+                hook_data,
+            } => Ok(
+                ArenaController::new(self.controller, transaction.provider.clone())
+                    .addLiquidity(liquidity_delta, tick_lower, tick_upper, hook_data)
+                    .into_transaction_request(),
+            ),
+        }
+    }
+
+    /// Mine pending transactions into a single block, in the given order, returning the inclusion outcome of each one.
+    ///
+    /// Setup such as minting and approving swap inputs is mined beforehand. Automine is then disabled while the transactions are sent, each with a fixed gas limit since they can't be estimated against each other, and a single block is mined.
+    /// Only as many transactions as fit in the block's gas limit are sent. The rest are dropped rather than left pending for a later block, and recorded as not included.
+    #[instrument(level = "debug", skip(self, transactions, admin), fields(transactions = transactions.len()))]
+    pub(crate) async fn build_block(
+        &self,
+        transactions: Vec<PendingTransaction>,
+        step: usize,
+        admin: &AnvilProvider,
+    ) -> Result<Vec<InclusionRecord>, ArenaError> {
+        let mut requests = Vec::with_capacity(transactions.len());
+
+        for transaction in &transactions {
+            requests.push(self.prepare(transaction).await);
+        }
+
+        let capacity = admin
+            .get_block_by_number(BlockNumberOrTag::Latest, false)
+            .await?
+            .map_or(usize::MAX, |block| {
+                (block.header.gas_limit / BLOCK_TRANSACTION_GAS_LIMIT) as usize
+            });
+
+        admin
+            .raw_request::<_, serde_json::Value>("evm_setAutomine".into(), (false,))
+            .await?;
+
+        let mut nonces: HashMap<Address, u64> = HashMap::new();
+        let mut hashes = Vec::with_capacity(transactions.len());
+        let mut sent = 0;
+
+        // each transaction gets its own span, entered both when it is sent and when its receipt is read
+        let spans: Vec<Span> = transactions
+            .iter()
+            .enumerate()
+            .map(|(position, transaction)| {
+                debug_span!("transaction", position, sender = %transaction.sender, tx_hash = Empty)
+            })
+            .collect();
+
+        for ((transaction, request), span) in transactions.iter().zip(requests).zip(&spans) {
+            let hash = async {
+                let hash = match request {
+                    Ok(_) if sent == capacity => Err("block gas limit reached".to_string()),
+                    Ok(request) => {
+                        let nonce = match nonces.get(&transaction.sender) {
+                            Some(nonce) => Ok(*nonce),
+                            None => nonce(&transaction.provider).await,
+                        };
+
+                        match nonce {
+                            Ok(nonce) => transaction
+                                .provider
+                                .send_transaction(
+                                    request
+                                        .with_nonce(nonce)
+                                        .with_gas_limit(BLOCK_TRANSACTION_GAS_LIMIT),
+                                )
+                                .await
+                                .map(|pending| {
+                                    nonces.insert(transaction.sender, nonce + 1);
+                                    *pending.tx_hash()
+                                })
+                                .map_err(|e| e.to_string()),
+                            Err(e) => Err(e.to_string()),
+                        }
+                    }
+                    Err(e) => Err(e.to_string()),
+                };
+
+                match &hash {
+                    Ok(hash) => {
+                        sent += 1;
+                        Span::current().record("tx_hash", display(hash));
+                        debug!("transaction submitted")
+                    }
+                    Err(e) => warn!(error = %e, "transaction not submitted"),
+                }
+
+                hash
+            }
+            .instrument(span.clone())
+            .await;
+
+            hashes.push(hash);
+        }
+
+        let mined = admin
+            .raw_request::<_, serde_json::Value>("evm_mine".into(), ())
+            .await;
+
+        // automine is restored before any error is returned, otherwise every later transaction would wait forever to be mined
+        admin
+            .raw_request::<_, serde_json::Value>("evm_setAutomine".into(), (true,))
+            .await?;
+        mined?;
+
+        let mut records = Vec::with_capacity(transactions.len());
+
+        for (position, ((transaction, hash), span)) in
+            transactions.into_iter().zip(hashes).zip(spans).enumerate()
+        {
+            let mut record = InclusionRecord {
+                step,
+                position,
+                sender: transaction.sender,
+                action: transaction.action,
+                priority_fee: transaction.priority_fee,
+                included: false,
+                success: false,
+                transaction_index: None,
+                gas_used: 0,
+                error: None,
+            };
+
+            async {
+                match hash {
+                    Ok(hash) => {
+                        if let Some(receipt) = admin.get_transaction_receipt(hash).await? {
+                            record.included = true;
+                            record.success = receipt.status();
+                            record.transaction_index = receipt.transaction_index;
+                            record.gas_used = receipt.gas_used;
+                        }
+                    }
+                    Err(e) => record.error = Some(e),
+                }
+
+                debug!(
+                    included = record.included,
+                    success = record.success,
+                    "transaction inclusion"
+                );
+
+                Ok::<_, ArenaError>(())
+            }
+            .instrument(span)
+            .await?;
+
+            records.push(record);
+        }
+
+        Ok(records)
+    }
+
+    async fn pool_key(&self, provider: AnvilProvider) -> Result<PoolKey, ArenaError> {
+        let key = ArenaController::new(self.controller, provider)
+            .poolKey()
+            .call()
+            .await?;
+
+        Ok(PoolKey {
+            currency0: key.currency0,
+            currency1: key.currency1,
+            fee: key.fee,
+            tickSpacing: key.tickSpacing,
+            hooks: key.hooks,
+        })
+    }
+}
+
+/// Approve `spender` to transfer the provider's tokens, if its allowance is below `amount`.
+async fn approve(
+    token: Address,
+    spender: Address,
+    amount: U256,
+    provider: &AnvilProvider,
+) -> Result<(), ArenaError> {
+    let token = ArenaToken::new(token, provider.clone());
+
+    if token
+        .allowance(provider.default_signer_address(), spender)
+        .call()
+        .await?
+        ._0
+        < amount
+    {
+        token
+            .approve(spender, U256::MAX)
+            .nonce(nonce(provider).await?)
+            .send()
+            .await?
+            .watch()
+            .await?;
+    }
+
+    Ok(())
+}
+
+/// Fetch the next nonce of the provider's default signer.
+async fn nonce(provider: &AnvilProvider) -> Result<u64, ArenaError> {
+    Ok(provider
+        .get_transaction_count(provider.default_signer_address())
+        .await?)
 }
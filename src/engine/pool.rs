@@ -0,0 +1,156 @@
+use alloy::primitives::{keccak256, Address, Signed, Uint, B256, I256, U256};
+use alloy_sol_types::SolValue;
+
+use crate::{
+    error::ArenaError,
+    types::{controller::ArenaController::PoolKey, manager::IPoolManager},
+    AnvilProvider,
+};
+
+/// Storage slot of the `pools` mapping in the PoolManager.
+const POOLS_SLOT: u64 = 6;
+
+/// Number of words at the start of a pool's `Pool.State` read as a [`PoolState`]: `slot0`, the global fee growth of each currency and the active liquidity.
+const STATE_WORDS: u64 = 4;
+
+/// Offset of the active liquidity within a pool's `Pool.State`.
+pub(crate) const LIQUIDITY_OFFSET: u64 = 3;
+
+/// Offset of the `tickBitmap` mapping within a pool's `Pool.State`.
+const TICK_BITMAP_OFFSET: u64 = 5;
+
+/// Offset of the `positions` mapping within a pool's `Pool.State`.
+const POSITIONS_OFFSET: u64 = 6;
+
+/// Compute the id of a pool from its key.
+pub(crate) fn pool_id(key: &PoolKey) -> B256 {
+    keccak256(key.abi_encode())
+}
+
+/// Compute the storage slot of a pool's `Pool.State` within the PoolManager.
+pub(crate) fn state_slot(pool_id: B256) -> U256 {
+    keccak256((pool_id, U256::from(POOLS_SLOT)).abi_encode()).into()
+}
+
+/// Compute the storage slot of a word of a pool's tick bitmap within the PoolManager.
+pub(crate) fn tick_bitmap_slot(pool_id: B256, word: i16) -> U256 {
+    let mapping = state_slot(pool_id) + U256::from(TICK_BITMAP_OFFSET);
+
+    keccak256((I256::try_from(word).unwrap(), mapping).abi_encode()).into()
+}
+
+/// Compute the storage slot of a position's `Position.State` within the PoolManager, which holds its liquidity followed by its last fee growth inside of each currency.
+pub(crate) fn position_slot(
+    pool_id: B256,
+    owner: Address,
+    tick_lower: i32,
+    tick_upper: i32,
+    salt: B256,
+) -> U256 {
+    // `Position.calculatePositionKey`, i.e. `abi.encodePacked(owner, int24 tickLower, int24 tickUpper, salt)`.
+    let mut packed = owner.to_vec();
+    packed.extend_from_slice(&tick_lower.to_be_bytes()[1..]);
+    packed.extend_from_slice(&tick_upper.to_be_bytes()[1..]);
+    packed.extend_from_slice(salt.as_slice());
+
+    let mapping = state_slot(pool_id) + U256::from(POSITIONS_OFFSET);
+
+    keccak256((keccak256(packed), mapping).abi_encode()).into()
+}
+
+/// Read `words` consecutive storage slots of the PoolManager, starting at `slot`, in a single call.
+pub(crate) async fn read(
+    manager: Address,
+    slot: U256,
+    words: u64,
+    provider: &AnvilProvider,
+) -> Result<Vec<U256>, ArenaError> {
+    let values = IPoolManager::new(manager, provider)
+        .extsload(slot.into(), U256::from(words))
+        .call()
+        .await?
+        .values;
+
+    Ok(values
+        .into_iter()
+        .map(|value| U256::from_be_bytes(value.0))
+        .collect())
+}
+
+/// The leading words of a pool's `Pool.State`, read from the PoolManager's storage in a single call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct PoolState {
+    pub(crate) sqrt_price_x96: Uint<160, 3>,
+    pub(crate) tick: Signed<24, 1>,
+    pub(crate) protocol_fee: Uint<24, 1>,
+    pub(crate) lp_fee: Uint<24, 1>,
+    pub(crate) fee_growth_global0_x128: U256,
+    pub(crate) fee_growth_global1_x128: U256,
+    pub(crate) liquidity: u128,
+}
+
+impl PoolState {
+    /// Read the state of a pool from the PoolManager.
+    pub(crate) async fn read(
+        manager: Address,
+        pool_id: B256,
+        provider: &AnvilProvider,
+    ) -> Result<Self, ArenaError> {
+        let words = read(manager, state_slot(pool_id), STATE_WORDS, provider).await?;
+
+        let words = words.try_into().map_err(|words: Vec<U256>| {
+            ArenaError::StateError(format!(
+                "expected {} words of pool state, read {}",
+                STATE_WORDS,
+                words.len()
+            ))
+        })?;
+
+        Ok(Self::decode(words))
+    }
+
+    /// Decode `slot0`, which packs the price into its lowest 160 bits followed by the tick, the protocol fee and the LP fee in 24 bits each, and the words after it.
+    fn decode(
+        [slot0, fee_growth_global0_x128, fee_growth_global1_x128, liquidity]: [U256; 4],
+    ) -> Self {
+        let bits = |offset: usize, len: usize| {
+            (slot0 >> offset) & ((U256::from(1) << len) - U256::from(1))
+        };
+
+        Self {
+            sqrt_price_x96: bits(0, 160).to(),
+            tick: Signed::from_raw(bits(160, 24).to()),
+            protocol_fee: bits(184, 24).to(),
+            lp_fee: bits(208, 24).to(),
+            fee_growth_global0_x128,
+            fee_growth_global1_x128,
+            liquidity: liquidity.to(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_pool_state() {
+        let sqrt_price_x96 = U256::from(1) << 96;
+        let tick = U256::from(-10_i32 as u32 & 0xFFFFFF);
+
+        let slot0 = sqrt_price_x96
+            | (tick << 160)
+            | (U256::from(0x00A00A) << 184)
+            | (U256::from(3000) << 208);
+
+        let state = PoolState::decode([slot0, U256::from(7), U256::from(8), U256::from(9)]);
+
+        assert_eq!(U256::from(state.sqrt_price_x96), sqrt_price_x96);
+        assert_eq!(state.tick.as_i32(), -10);
+        assert_eq!(state.protocol_fee.to::<u32>(), 0x00A00A);
+        assert_eq!(state.lp_fee.to::<u32>(), 3000);
+        assert_eq!(state.fee_growth_global0_x128, U256::from(7));
+        assert_eq!(state.fee_growth_global1_x128, U256::from(8));
+        assert_eq!(state.liquidity, 9);
+    }
+}
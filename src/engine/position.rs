@@ -0,0 +1,169 @@
+use alloy::primitives::{Address, B256, U256, U512};
+use serde::{Deserialize, Serialize};
+
+use super::{
+    math::{amount0_delta, amount1_delta, sqrt_price_at_tick},
+    ticks::TickInfo,
+};
+
+/// A liquidity position in the pool, valued at the current pool price.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    /// Owner of the position, i.e. the address that modified liquidity on the PoolManager.
+    pub owner: Address,
+
+    /// Lower tick of the position.
+    pub tick_lower: i32,
+
+    /// Upper tick of the position.
+    pub tick_upper: i32,
+
+    /// Salt distinguishing positions of the same owner and range.
+    pub salt: B256,
+
+    /// Liquidity of the position.
+    pub liquidity: u128,
+
+    /// Fee growth of currency0 inside the range as of the position's last update, as a Q128.128.
+    pub fee_growth_inside0_last_x128: U256,
+
+    /// Fee growth of currency1 inside the range as of the position's last update, as a Q128.128.
+    pub fee_growth_inside1_last_x128: U256,
+
+    /// Amount of currency0 that the position's liquidity is worth at the current pool price.
+    pub amount0: U256,
+
+    /// Amount of currency1 that the position's liquidity is worth at the current pool price.
+    pub amount1: U256,
+
+    /// Fees of currency0 earned since the position's last update that have not been collected.
+    pub fees0: U256,
+
+    /// Fees of currency1 earned since the position's last update that have not been collected.
+    pub fees1: U256,
+}
+
+/// Compute the fee growth of both currencies inside a tick range, like `Pool.getFeeGrowthInside`.
+///
+/// Fee growth values are allowed to overflow, so all arithmetic wraps.
+pub fn fee_growth_inside(
+    current_tick: i32,
+    lower: &TickInfo,
+    upper: &TickInfo,
+    fee_growth_global0_x128: U256,
+    fee_growth_global1_x128: U256,
+) -> (U256, U256) {
+    let inside = |global: U256, lower_outside: U256, upper_outside: U256| {
+        if current_tick < lower.tick {
+            lower_outside.wrapping_sub(upper_outside)
+        } else if current_tick >= upper.tick {
+            upper_outside.wrapping_sub(lower_outside)
+        } else {
+            global
+                .wrapping_sub(lower_outside)
+                .wrapping_sub(upper_outside)
+        }
+    };
+
+    (
+        inside(
+            fee_growth_global0_x128,
+            lower.fee_growth_outside0_x128,
+            upper.fee_growth_outside0_x128,
+        ),
+        inside(
+            fee_growth_global1_x128,
+            lower.fee_growth_outside1_x128,
+            upper.fee_growth_outside1_x128,
+        ),
+    )
+}
+
+/// Compute the fees owed to a position from the fee growth inside its range since its last update.
+pub fn fees_owed(
+    liquidity: u128,
+    fee_growth_inside_x128: U256,
+    fee_growth_inside_last_x128: U256,
+) -> U256 {
+    let growth = fee_growth_inside_x128.wrapping_sub(fee_growth_inside_last_x128);
+
+    let owed: U512 = (U512::from(growth) * U512::from(liquidity)) >> 128;
+
+    owed.to()
+}
+
+/// Compute the amounts of both currencies that liquidity in a tick range is worth at the current pool price, rounding down.
+pub fn amounts_for_liquidity(
+    current_tick: i32,
+    sqrt_price_x96: U256,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+) -> (U256, U256) {
+    let sqrt_price_lower = sqrt_price_at_tick(tick_lower);
+    let sqrt_price_upper = sqrt_price_at_tick(tick_upper);
+
+    if current_tick < tick_lower {
+        (
+            amount0_delta(sqrt_price_lower, sqrt_price_upper, liquidity, false),
+            U256::ZERO,
+        )
+    } else if current_tick >= tick_upper {
+        (
+            U256::ZERO,
+            amount1_delta(sqrt_price_lower, sqrt_price_upper, liquidity, false),
+        )
+    } else {
+        (
+            amount0_delta(sqrt_price_x96, sqrt_price_upper, liquidity, false),
+            amount1_delta(sqrt_price_lower, sqrt_price_x96, liquidity, false),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(tick: i32, outside0: U256, outside1: U256) -> TickInfo {
+        TickInfo {
+            tick,
+            liquidity_gross: 1,
+            liquidity_net: 1,
+            fee_growth_outside0_x128: outside0,
+            fee_growth_outside1_x128: outside1,
+        }
+    }
+
+    #[test]
+    fn test_fees_owed_inside_range() {
+        let q128 = U256::from(1) << 128;
+
+        let lower = tick(-60, q128, U256::ZERO);
+        let upper = tick(60, U256::from(2) * q128, q128);
+
+        // Global growth of 10 and 3 per unit of liquidity, of which 7 and 2 accrued inside the range.
+        let (inside0, inside1) = fee_growth_inside(
+            0,
+            &lower,
+            &upper,
+            U256::from(10) * q128,
+            U256::from(3) * q128,
+        );
+
+        assert_eq!(inside0, U256::from(7) * q128);
+        assert_eq!(inside1, U256::from(2) * q128);
+
+        // Fee growth wraps around, so a last value above the current one still yields the accrued difference.
+        let last = U256::MAX - q128 + U256::from(1);
+        assert_eq!(fees_owed(1000, inside0, last), U256::from(8000));
+        assert_eq!(fees_owed(1000, inside1, U256::ZERO), U256::from(2000));
+
+        // Above the range, the position holds only currency1.
+        let (amount0, amount1) =
+            amounts_for_liquidity(120, sqrt_price_at_tick(120), -60, 60, 10_u128.pow(18));
+
+        assert!(amount0.is_zero());
+        assert!(amount1 > U256::ZERO);
+    }
+}
@@ -0,0 +1,262 @@
+use std::{collections::HashMap, fmt};
+
+use alloy::primitives::U256;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    engine::inspector::{Inspector, LogMessage},
+    error::ArenaError,
+};
+
+/// Typed value of a telemetry metric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum Value {
+    /// A floating point value, such as a price.
+    Float(f64),
+
+    /// A signed integer value, such as a tick.
+    Int(i64),
+
+    /// An unsigned 256 bit value, such as a token amount.
+    Uint(U256),
+
+    /// A boolean flag.
+    Bool(bool),
+
+    /// Arbitrary structured data.
+    Json(serde_json::Value),
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Float(value) => write!(f, "{}", value),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::Uint(value) => write!(f, "{}", value),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Json(value) => write!(f, "{}", value),
+        }
+    }
+}
+
+impl From<f64> for Value {
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<i64> for Value {
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+impl From<U256> for Value {
+    fn from(value: U256) -> Self {
+        Value::Uint(value)
+    }
+}
+
+impl From<bool> for Value {
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<serde_json::Value> for Value {
+    fn from(value: serde_json::Value) -> Self {
+        Value::Json(value)
+    }
+}
+
+/// A single telemetry metric observed at a point of the simulation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Record {
+    /// Step of the simulation at which the metric was observed, if it was observed during a step.
+    pub step: Option<usize>,
+
+    /// Simulated time at which the metric was observed.
+    pub time: f64,
+
+    /// Strategy that observed the metric. Set by a [`ScopedInspector`](crate::engine::inspector::ScopedInspector) if left empty.
+    pub strategy: Option<String>,
+
+    /// Name of the metric.
+    pub metric: String,
+
+    /// Value of the metric.
+    pub value: Value,
+}
+
+impl Record {
+    /// Public constructor function for a new [`Record`].
+    pub fn new(step: Option<usize>, time: f64, metric: String, value: impl Into<Value>) -> Self {
+        Self {
+            step,
+            time,
+            strategy: None,
+            metric,
+            value: value.into(),
+        }
+    }
+
+    /// Set the strategy that observed the metric.
+    pub fn with_strategy(mut self, strategy: String) -> Self {
+        self.strategy = Some(strategy);
+        self
+    }
+}
+
+impl From<Record> for LogMessage {
+    fn from(record: Record) -> Self {
+        let data = serde_json::to_string(&record).unwrap_or_default();
+
+        let mut message = LogMessage::new(record.metric, data);
+        message.scope = record.strategy;
+
+        message
+    }
+}
+
+/// Implementation of an [`Inspector`] that writes [`Record`]s to a CSV file with one row per step and strategy, and one column per metric.
+///
+/// The columns are only known once every metric has been seen, so the file is written in full on [`Inspector::save`]. Metrics keep the order they were first logged in, and cells of metrics not observed on a row are left empty.
+#[derive(Debug)]
+pub struct RecordWriter {
+    file_path: String,
+    records: Vec<Record>,
+    metrics: Vec<String>,
+}
+
+impl RecordWriter {
+    /// Public constructor function for a new [`RecordWriter`].
+    pub fn new(file_path: String) -> Self {
+        Self {
+            file_path,
+            records: Vec::new(),
+            metrics: Vec::new(),
+        }
+    }
+
+    /// Names of the metrics logged so far, in the order of the file's metric columns.
+    pub fn metrics(&self) -> &[String] {
+        &self.metrics
+    }
+
+    /// Write every record logged so far to the CSV file, replacing its contents.
+    pub fn write(&self) -> Result<(), ArenaError> {
+        let mut writer = csv::Writer::from_path(&self.file_path)?;
+
+        let mut header = vec![
+            "step".to_string(),
+            "time".to_string(),
+            "strategy".to_string(),
+        ];
+        header.extend(self.metrics.iter().cloned());
+        writer.write_record(&header)?;
+
+        let columns: HashMap<&str, usize> = self
+            .metrics
+            .iter()
+            .enumerate()
+            .map(|(idx, metric)| (metric.as_str(), idx))
+            .collect();
+
+        let mut rows: Vec<(&Record, Vec<String>)> = Vec::new();
+        let mut index: HashMap<(Option<usize>, Option<&str>), usize> = HashMap::new();
+
+        for record in &self.records {
+            let key = (record.step, record.strategy.as_deref());
+
+            let row = *index.entry(key).or_insert_with(|| {
+                rows.push((record, vec![String::new(); columns.len()]));
+                rows.len() - 1
+            });
+
+            rows[row].1[columns[record.metric.as_str()]] = record.value.to_string();
+        }
+
+        for (first, cells) in rows {
+            let mut row = vec![
+                first.step.map(|step| step.to_string()).unwrap_or_default(),
+                first.time.to_string(),
+                first.strategy.clone().unwrap_or_default(),
+            ];
+            row.extend(cells);
+
+            writer.write_record(&row)?;
+        }
+
+        writer.flush()?;
+
+        Ok(())
+    }
+}
+
+impl Inspector<Record> for RecordWriter {
+    fn log(&mut self, record: Record) {
+        if !self.metrics.contains(&record.metric) {
+            self.metrics.push(record.metric.clone());
+        }
+
+        self.records.push(record);
+    }
+
+    fn log_scoped(&mut self, scope: &str, mut record: Record) {
+        record.strategy.get_or_insert_with(|| scope.to_string());
+        self.log(record);
+    }
+
+    fn log_record(&mut self, record: Record) {
+        self.log(record);
+    }
+
+    fn inspect(&self, step: usize) -> Option<Record> {
+        self.records.get(step).cloned()
+    }
+
+    fn inspect_scoped(&self, scope: &str, step: usize) -> Option<Record> {
+        self.records
+            .iter()
+            .filter(|record| record.strategy.as_deref() == Some(scope))
+            .nth(step)
+            .cloned()
+    }
+
+    fn save(&mut self) -> Result<(), ArenaError> {
+        self.write()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_record_writer_pivots_metrics() {
+        let path = std::env::temp_dir().join("arena_test_record_writer_pivots_metrics.csv");
+        let path = path.to_str().unwrap().to_string();
+
+        let mut writer = RecordWriter::new(path.clone());
+
+        writer.log_scoped("lp", Record::new(Some(0), 0.0, "price".to_string(), 1.5));
+        writer.log_scoped(
+            "lp",
+            Record::new(Some(0), 0.0, "in_range".to_string(), true),
+        );
+        writer.log_scoped(
+            "lp",
+            Record::new(Some(1), 0.1, "fees".to_string(), U256::from(7)),
+        );
+        writer.save().unwrap();
+
+        let contents = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(
+            contents,
+            "step,time,strategy,price,in_range,fees\n0,0,lp,1.5,true,\n1,0.1,lp,,,7\n"
+        );
+    }
+}
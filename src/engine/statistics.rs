@@ -0,0 +1,339 @@
+use std::collections::{HashMap, VecDeque};
+
+use serde::{Deserialize, Serialize};
+
+use crate::engine::{
+    inspector::Inspector,
+    record::{Record, Value},
+};
+
+/// Snapshot of the statistics of a single metric.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+    /// Number of observations.
+    pub count: usize,
+
+    /// Mean of every observation.
+    pub mean: f64,
+
+    /// Unbiased sample variance of every observation.
+    pub variance: f64,
+
+    /// Exponentially weighted moving average.
+    pub ewma: f64,
+
+    /// Smallest observation.
+    pub min: f64,
+
+    /// Largest observation.
+    pub max: f64,
+
+    /// Mean of the observations in the rolling window.
+    pub window_mean: f64,
+
+    /// Unbiased sample variance of the observations in the rolling window.
+    pub window_variance: f64,
+
+    /// Estimated quantiles, as `(probability, value)` pairs.
+    pub quantiles: Vec<(f64, f64)>,
+}
+
+impl Statistics {
+    /// Standard deviation of every observation.
+    pub fn std_dev(&self) -> f64 {
+        self.variance.sqrt()
+    }
+
+    /// Standard deviation of the observations in the rolling window, such as realized volatility when the metric is a log return.
+    pub fn window_std_dev(&self) -> f64 {
+        self.window_variance.sqrt()
+    }
+
+    /// Estimated value of the quantile with probability `p`, if it is tracked.
+    pub fn quantile(&self, p: f64) -> Option<f64> {
+        self.quantiles
+            .iter()
+            .find(|(probability, _)| *probability == p)
+            .map(|(_, value)| *value)
+    }
+}
+
+/// Implementation of an [`Inspector`] that maintains online statistics of every numeric metric logged to it.
+///
+/// Each metric tracks its mean and variance with Welford's algorithm, an exponentially weighted moving average, its extremes, the mean and variance of a rolling window of recent observations, and quantiles estimated with the P² sketch, so its memory is bounded by the window size whatever the length of the simulation.
+/// The records themselves are not kept, so [`Inspector::inspect`] always returns `None`.
+/// Metrics logged through a [`ScopedInspector`](crate::engine::inspector::ScopedInspector) are kept per strategy, so strategies can query their own statistics with [`Inspector::statistics`] while the simulation runs.
+/// Integer, unsigned and boolean values are converted to floats, and JSON values are ignored.
+#[derive(Debug)]
+pub struct RollingStatistics {
+    alpha: f64,
+    window: usize,
+    probabilities: Vec<f64>,
+    metrics: HashMap<String, Accumulator>,
+}
+
+impl RollingStatistics {
+    /// Public constructor function for a new [`RollingStatistics`], with EWMA smoothing factor `alpha`, a rolling window of `window` observations, and the median, 5% and 95% quantiles.
+    pub fn new(alpha: f64, window: usize) -> Self {
+        Self {
+            alpha: alpha.clamp(0.0, 1.0),
+            window: window.max(1),
+            probabilities: vec![0.05, 0.5, 0.95],
+            metrics: HashMap::new(),
+        }
+    }
+
+    /// Set the probabilities of the quantiles estimated for each metric.
+    pub fn with_quantiles(mut self, probabilities: Vec<f64>) -> Self {
+        self.probabilities = probabilities;
+        self
+    }
+
+    fn key(record: &Record) -> String {
+        match &record.strategy {
+            Some(strategy) => format!("{}/{}", strategy, record.metric),
+            None => record.metric.clone(),
+        }
+    }
+}
+
+impl Inspector<Record> for RollingStatistics {
+    fn log(&mut self, record: Record) {
+        let value = match &record.value {
+            Value::Float(value) => *value,
+            Value::Int(value) => *value as f64,
+            Value::Uint(value) => f64::from(*value),
+            Value::Bool(value) => f64::from(u8::from(*value)),
+            Value::Json(_) => return,
+        };
+
+        self.metrics
+            .entry(Self::key(&record))
+            .or_insert_with(|| Accumulator::new(self.window, &self.probabilities))
+            .push(value, self.alpha);
+    }
+
+    fn log_scoped(&mut self, scope: &str, mut record: Record) {
+        record.strategy.get_or_insert_with(|| scope.to_string());
+        self.log(record);
+    }
+
+    fn inspect(&self, _step: usize) -> Option<Record> {
+        None
+    }
+
+    fn statistics(&self, metric: &str) -> Option<Statistics> {
+        self.metrics.get(metric).map(Accumulator::statistics)
+    }
+}
+
+/// Online statistics of a single metric.
+#[derive(Debug)]
+struct Accumulator {
+    count: usize,
+    mean: f64,
+    m2: f64,
+    ewma: f64,
+    min: f64,
+    max: f64,
+    window: usize,
+    recent: VecDeque<f64>,
+    quantiles: Vec<P2Quantile>,
+}
+
+impl Accumulator {
+    fn new(window: usize, probabilities: &[f64]) -> Self {
+        Self {
+            count: 0,
+            mean: 0.0,
+            m2: 0.0,
+            ewma: 0.0,
+            min: f64::INFINITY,
+            max: f64::NEG_INFINITY,
+            window,
+            recent: VecDeque::with_capacity(window),
+            quantiles: probabilities.iter().map(|p| P2Quantile::new(*p)).collect(),
+        }
+    }
+
+    fn push(&mut self, value: f64, alpha: f64) {
+        self.count += 1;
+
+        let delta = value - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (value - self.mean);
+
+        self.ewma = if self.count == 1 {
+            value
+        } else {
+            alpha * value + (1.0 - alpha) * self.ewma
+        };
+
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+
+        if self.recent.len() == self.window {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(value);
+
+        for quantile in self.quantiles.iter_mut() {
+            quantile.push(value);
+        }
+    }
+
+    fn statistics(&self) -> Statistics {
+        let n = self.recent.len() as f64;
+        let window_mean = self.recent.iter().sum::<f64>() / n;
+        let window_variance = if self.recent.len() > 1 {
+            self.recent
+                .iter()
+                .map(|value| (value - window_mean).powi(2))
+                .sum::<f64>()
+                / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        Statistics {
+            count: self.count,
+            mean: self.mean,
+            variance: if self.count > 1 {
+                self.m2 / (self.count - 1) as f64
+            } else {
+                0.0
+            },
+            ewma: self.ewma,
+            min: self.min,
+            max: self.max,
+            window_mean,
+            window_variance,
+            quantiles: self
+                .quantiles
+                .iter()
+                .map(|quantile| (quantile.p, quantile.estimate()))
+                .collect(),
+        }
+    }
+}
+
+/// Streaming estimate of a single quantile with the P² algorithm of Jain and Chlamtac, using five markers.
+#[derive(Debug)]
+struct P2Quantile {
+    p: f64,
+    heights: Vec<f64>,
+    positions: [f64; 5],
+    desired: [f64; 5],
+    increments: [f64; 5],
+}
+
+impl P2Quantile {
+    fn new(p: f64) -> Self {
+        Self {
+            p,
+            heights: Vec::with_capacity(5),
+            positions: [1.0, 2.0, 3.0, 4.0, 5.0],
+            desired: [1.0, 1.0 + 2.0 * p, 1.0 + 4.0 * p, 3.0 + 2.0 * p, 5.0],
+            increments: [0.0, p / 2.0, p, (1.0 + p) / 2.0, 1.0],
+        }
+    }
+
+    fn push(&mut self, value: f64) {
+        if self.heights.len() < 5 {
+            self.heights.push(value);
+            self.heights.sort_by(f64::total_cmp);
+            return;
+        }
+
+        let q = &mut self.heights;
+
+        let k = if value < q[0] {
+            q[0] = value;
+            0
+        } else if value >= q[4] {
+            q[4] = value;
+            3
+        } else {
+            (0..4).find(|i| value < q[i + 1]).unwrap_or(3)
+        };
+
+        for position in self.positions[k + 1..].iter_mut() {
+            *position += 1.0;
+        }
+        for (desired, increment) in self.desired.iter_mut().zip(self.increments) {
+            *desired += increment;
+        }
+
+        let n = &mut self.positions;
+
+        for i in 1..4 {
+            let d = self.desired[i] - n[i];
+
+            if (d >= 1.0 && n[i + 1] - n[i] > 1.0) || (d <= -1.0 && n[i - 1] - n[i] < -1.0) {
+                let d = d.signum();
+
+                let parabolic = q[i]
+                    + d / (n[i + 1] - n[i - 1])
+                        * ((n[i] - n[i - 1] + d) * (q[i + 1] - q[i]) / (n[i + 1] - n[i])
+                            + (n[i + 1] - n[i] - d) * (q[i] - q[i - 1]) / (n[i] - n[i - 1]));
+
+                q[i] = if q[i - 1] < parabolic && parabolic < q[i + 1] {
+                    parabolic
+                } else {
+                    let j = if d > 0.0 { i + 1 } else { i - 1 };
+                    q[i] + d * (q[j] - q[i]) / (n[j] - n[i])
+                };
+
+                n[i] += d;
+            }
+        }
+    }
+
+    fn estimate(&self) -> f64 {
+        match self.heights.len() {
+            0 => f64::NAN,
+            5.. if self.positions[4] > 5.0 => self.heights[2],
+            len => self.heights[((len - 1) as f64 * self.p).round() as usize],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    use super::*;
+
+    #[test]
+    fn test_rolling_statistics_of_normal_samples() {
+        let mut rng = StdRng::seed_from_u64(0);
+        let normal = Normal::new(2.0, 3.0).unwrap();
+
+        let mut statistics = RollingStatistics::new(0.1, 100);
+
+        for step in 0..20_000 {
+            statistics.log_scoped(
+                "lp",
+                Record::new(
+                    Some(step),
+                    0.0,
+                    "return".to_string(),
+                    normal.sample(&mut rng),
+                ),
+            );
+        }
+
+        assert!(statistics.statistics("return").is_none());
+
+        let summary = statistics.statistics("lp/return").unwrap();
+
+        assert_eq!(summary.count, 20_000);
+        assert!((summary.mean - 2.0).abs() < 0.1);
+        assert!((summary.std_dev() - 3.0).abs() < 0.1);
+        assert!((summary.window_std_dev() - 3.0).abs() < 1.0);
+        assert!(summary.min < -7.0 && summary.max > 11.0);
+        assert!((summary.quantile(0.5).unwrap() - 2.0).abs() < 0.1);
+        assert!((summary.quantile(0.95).unwrap() - (2.0 + 3.0 * 1.645)).abs() < 0.2);
+    }
+}
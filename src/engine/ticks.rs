@@ -0,0 +1,141 @@
+use alloy::primitives::U256;
+use serde::{Deserialize, Serialize};
+
+use super::math::{sqrt_price_at_tick, sqrt_price_x96_to_price};
+
+/// The state of a single tick of the pool, as returned by `Fetcher.getTickInfo`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickInfo {
+    /// The tick.
+    pub tick: i32,
+
+    /// Total liquidity of the positions referencing the tick.
+    pub liquidity_gross: u128,
+
+    /// Liquidity added to the active liquidity when the tick is crossed from left to right.
+    pub liquidity_net: i128,
+
+    /// Fee growth of currency0 on the other side of the tick from the current tick, as a Q128.128.
+    pub fee_growth_outside0_x128: U256,
+
+    /// Fee growth of currency1 on the other side of the tick from the current tick, as a Q128.128.
+    pub fee_growth_outside1_x128: U256,
+}
+
+/// A price range of the pool over which the active liquidity is constant.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LiquidityBucket {
+    /// Lower tick of the range, inclusive.
+    pub tick_lower: i32,
+
+    /// Upper tick of the range, exclusive.
+    pub tick_upper: i32,
+
+    /// Price of token0 in terms of token1 at the lower tick.
+    pub price_lower: f64,
+
+    /// Price of token0 in terms of token1 at the upper tick.
+    pub price_upper: f64,
+
+    /// Active liquidity whenever the pool's tick is within the range.
+    pub liquidity: u128,
+}
+
+/// Build the depth profile of a pool between two ticks, from its initialized ticks and active liquidity.
+///
+/// `ticks` must contain every initialized tick between `current_tick` and the range, since the liquidity of each bucket is found by crossing them outwards from the current tick.
+/// One bucket is returned per range between consecutive initialized ticks.
+pub fn liquidity_distribution(
+    ticks: &[TickInfo],
+    current_tick: i32,
+    active_liquidity: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Vec<LiquidityBucket> {
+    let liquidity_at = |tick: i32| {
+        let crossed: i128 = if tick >= current_tick {
+            ticks
+                .iter()
+                .filter(|info| info.tick > current_tick && info.tick <= tick)
+                .map(|info| info.liquidity_net)
+                .sum()
+        } else {
+            -ticks
+                .iter()
+                .filter(|info| info.tick > tick && info.tick <= current_tick)
+                .map(|info| info.liquidity_net)
+                .sum::<i128>()
+        };
+
+        u128::try_from(active_liquidity as i128 + crossed).unwrap_or_default()
+    };
+
+    let mut boundaries = vec![tick_lower];
+    boundaries.extend(
+        ticks
+            .iter()
+            .map(|info| info.tick)
+            .filter(|tick| *tick > tick_lower && *tick < tick_upper),
+    );
+    boundaries.push(tick_upper);
+    boundaries.sort_unstable();
+    boundaries.dedup();
+
+    boundaries
+        .windows(2)
+        .map(|range| LiquidityBucket {
+            tick_lower: range[0],
+            tick_upper: range[1],
+            price_lower: sqrt_price_x96_to_price(sqrt_price_at_tick(range[0])),
+            price_upper: sqrt_price_x96_to_price(sqrt_price_at_tick(range[1])),
+            liquidity: liquidity_at(range[0]),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tick(tick: i32, liquidity_net: i128) -> TickInfo {
+        TickInfo {
+            tick,
+            liquidity_gross: liquidity_net.unsigned_abs(),
+            liquidity_net,
+            fee_growth_outside0_x128: U256::ZERO,
+            fee_growth_outside1_x128: U256::ZERO,
+        }
+    }
+
+    #[test]
+    fn test_liquidity_distribution() {
+        // Two overlapping positions, [-120, 60) with 100 and [-60, 120) with 50.
+        let ticks = [
+            tick(-120, 100),
+            tick(-60, 50),
+            tick(60, -100),
+            tick(120, -50),
+        ];
+
+        let buckets = liquidity_distribution(&ticks, 0, 150, -180, 180);
+
+        let profile: Vec<_> = buckets
+            .iter()
+            .map(|bucket| (bucket.tick_lower, bucket.tick_upper, bucket.liquidity))
+            .collect();
+
+        assert_eq!(
+            profile,
+            vec![
+                (-180, -120, 0),
+                (-120, -60, 100),
+                (-60, 60, 150),
+                (60, 120, 50),
+                (120, 180, 0),
+            ]
+        );
+
+        assert!(buckets[0].price_lower < buckets[0].price_upper);
+        assert_eq!(buckets[2].price_upper, buckets[3].price_lower);
+    }
+}
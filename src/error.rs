@@ -11,7 +11,53 @@ pub enum ArenaError {
     #[error("alloy pending transaction error {0}")]
     PendingTransactionError(#[from] alloy::providers::PendingTransactionError),
 
+    /// RPC transport error.
+    #[error("alloy transport error {0}")]
+    TransportError(#[from] alloy::transports::TransportError),
+
+    /// Conversion error when converting between integer types.
+    #[error("alloy integer conversion error {0}")]
+    IntegerConversionError(#[from] alloy::primitives::BigIntConversionError),
+
     /// Conversion error when parsing ether values.
     #[error("alloy conversion error {0}")]
     ConversionError(#[from] alloy::primitives::utils::UnitsError),
+
+    /// Reading a CSV file failed.
+    #[error("csv error {0}")]
+    CsvError(#[from] csv::Error),
+
+    /// Reading or writing a file failed.
+    #[error("io error {0}")]
+    IoError(#[from] std::io::Error),
+
+    /// Serializing or deserializing JSON failed.
+    #[error("json error {0}")]
+    JsonError(#[from] serde_json::Error),
+
+    /// Building or writing Arrow data failed.
+    #[cfg(feature = "columnar")]
+    #[error("arrow error {0}")]
+    ArrowError(#[from] arrow::error::ArrowError),
+
+    /// Writing a Parquet file failed.
+    #[cfg(feature = "columnar")]
+    #[error("parquet error {0}")]
+    ParquetError(#[from] parquet::errors::ParquetError),
+
+    /// The contracts deployed by the controller could not be resolved.
+    #[error("deployment error {0}")]
+    DeploymentError(String),
+
+    /// State read from the chain could not be decoded, such as storage words of the wrong length.
+    #[error("state error {0}")]
+    StateError(String),
+
+    /// An argument was outside of its valid range.
+    #[error("invalid input {0}")]
+    InvalidInput(String),
+
+    /// Fitting a feed to historical data failed.
+    #[error("calibration error {0}")]
+    CalibrationError(String),
 }
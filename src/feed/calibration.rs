@@ -0,0 +1,331 @@
+use std::{f64::consts::PI, path::Path};
+
+use super::*;
+use crate::error::ArenaError;
+
+/// Load a historical price series from a named column of a CSV file with headers.
+pub fn load_prices<P: AsRef<Path>>(path: P, column: &str) -> Result<Vec<f64>, ArenaError> {
+    let mut reader = csv::Reader::from_path(path)?;
+
+    let index = reader
+        .headers()?
+        .iter()
+        .position(|header| header.trim() == column)
+        .ok_or_else(|| ArenaError::CalibrationError(format!("column `{}` not found", column)))?;
+
+    reader
+        .records()
+        .map(|record| {
+            let record = record?;
+            let field = record.get(index).unwrap_or_default().trim();
+
+            field.parse::<f64>().map_err(|_| {
+                ArenaError::CalibrationError(format!(
+                    "invalid price `{}` in column `{}`",
+                    field, column
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Trait for feeds whose parameters can be estimated from a historical price series.
+pub trait Calibrate: Feed + Sized {
+    /// Fit the feed to `prices`, sampled every `dt` units of simulated time.
+    ///
+    /// The returned feed starts at the first observed price, so simulated paths are directly comparable to the history.
+    fn calibrate(prices: &[f64], dt: f64) -> Result<Calibration<Self>, ArenaError>;
+}
+
+/// A feed fitted to historical data, along with diagnostics on how well it fits.
+#[derive(Debug)]
+pub struct Calibration<F> {
+    /// The fitted feed.
+    pub feed: F,
+
+    /// Goodness-of-fit diagnostics for the fitted feed.
+    pub diagnostics: Diagnostics,
+}
+
+/// Goodness-of-fit diagnostics, computed from the standardized residuals of a fitted model.
+///
+/// If the model is well specified the residuals are i.i.d. standard normal.
+#[derive(Debug, Clone)]
+pub struct Diagnostics {
+    /// Number of transitions used in the fit.
+    pub observations: usize,
+
+    /// Maximized log-likelihood of the fitted model.
+    pub log_likelihood: f64,
+
+    /// Akaike information criterion.
+    pub aic: f64,
+
+    /// Bayesian information criterion.
+    pub bic: f64,
+
+    /// Mean of the standardized residuals.
+    pub residual_mean: f64,
+
+    /// Standard deviation of the standardized residuals.
+    pub residual_std: f64,
+
+    /// Skewness of the standardized residuals.
+    pub skewness: f64,
+
+    /// Excess kurtosis of the standardized residuals, positive for fat tails.
+    pub excess_kurtosis: f64,
+
+    /// Jarque-Bera normality test statistic, asymptotically chi-squared with two degrees of freedom.
+    pub jarque_bera: f64,
+
+    /// Lag-one autocorrelation of the standardized residuals.
+    pub autocorrelation: f64,
+
+    /// Kolmogorov-Smirnov distance between the standardized residuals and a standard normal.
+    pub ks_statistic: f64,
+}
+
+impl Diagnostics {
+    fn new(residuals: &[f64], log_likelihood: f64, parameters: usize) -> Self {
+        let n = residuals.len() as f64;
+
+        let mean = residuals.iter().sum::<f64>() / n;
+        let moment = |k: i32| residuals.iter().map(|z| (z - mean).powi(k)).sum::<f64>() / n;
+
+        let variance = moment(2);
+        let skewness = moment(3) / variance.powf(1.5);
+        let excess_kurtosis = moment(4) / variance.powi(2) - 3.0;
+
+        let autocorrelation = residuals
+            .windows(2)
+            .map(|w| (w[0] - mean) * (w[1] - mean))
+            .sum::<f64>()
+            / (n * variance);
+
+        let mut sorted = residuals.to_vec();
+        sorted.sort_by(|a, b| a.total_cmp(b));
+
+        let ks_statistic = sorted
+            .iter()
+            .enumerate()
+            .map(|(i, z)| {
+                let cdf = normal_cdf(*z);
+                (((i + 1) as f64 / n) - cdf).max(cdf - i as f64 / n)
+            })
+            .fold(0.0, f64::max);
+
+        Self {
+            observations: residuals.len(),
+            log_likelihood,
+            aic: 2.0 * parameters as f64 - 2.0 * log_likelihood,
+            bic: parameters as f64 * n.ln() - 2.0 * log_likelihood,
+            residual_mean: mean,
+            residual_std: variance.sqrt(),
+            skewness,
+            excess_kurtosis,
+            jarque_bera: n / 6.0 * (skewness.powi(2) + excess_kurtosis.powi(2) / 4.0),
+            autocorrelation,
+            ks_statistic,
+        }
+    }
+}
+
+impl Calibrate for GeometricBrownianMotion {
+    /// Maximum likelihood estimates from the log-returns of the series.
+    fn calibrate(prices: &[f64], dt: f64) -> Result<Calibration<Self>, ArenaError> {
+        validate(prices, dt)?;
+
+        if prices.iter().any(|price| *price <= 0.0) {
+            return Err(ArenaError::CalibrationError(
+                "geometric brownian motion requires strictly positive prices".to_string(),
+            ));
+        }
+
+        let returns: Vec<f64> = prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let n = returns.len() as f64;
+
+        let mean = returns.iter().sum::<f64>() / n;
+        let variance = returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / n;
+
+        if variance <= 0.0 {
+            return Err(ArenaError::CalibrationError(
+                "price series has no variance".to_string(),
+            ));
+        }
+
+        let sigma = (variance / dt).sqrt();
+        let mu = mean / dt + 0.5 * sigma.powi(2);
+
+        let residuals: Vec<f64> = returns
+            .iter()
+            .map(|r| (r - mean) / variance.sqrt())
+            .collect();
+
+        let log_likelihood = -0.5 * n * ((2.0 * PI * variance).ln() + 1.0);
+
+        Ok(Calibration {
+            feed: GeometricBrownianMotion::new(prices[0], mu, sigma, dt),
+            diagnostics: Diagnostics::new(&residuals, log_likelihood, 2),
+        })
+    }
+}
+
+impl Calibrate for OrnsteinUhlenbeck {
+    /// Maximum likelihood estimates of the Euler-Maruyama transition density, obtained by regressing each price on the previous one.
+    fn calibrate(prices: &[f64], dt: f64) -> Result<Calibration<Self>, ArenaError> {
+        validate(prices, dt)?;
+
+        let (x, y) = (&prices[..prices.len() - 1], &prices[1..]);
+        let n = x.len() as f64;
+
+        let x_mean = x.iter().sum::<f64>() / n;
+        let y_mean = y.iter().sum::<f64>() / n;
+
+        let covariance = x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| (x - x_mean) * (y - y_mean))
+            .sum::<f64>();
+        let variance = x.iter().map(|x| (x - x_mean).powi(2)).sum::<f64>();
+
+        if variance <= 0.0 {
+            return Err(ArenaError::CalibrationError(
+                "price series has no variance".to_string(),
+            ));
+        }
+
+        let slope = covariance / variance;
+        let intercept = y_mean - slope * x_mean;
+
+        if slope >= 1.0 {
+            return Err(ArenaError::CalibrationError(
+                "price series is not mean-reverting".to_string(),
+            ));
+        }
+
+        let errors: Vec<f64> = x
+            .iter()
+            .zip(y)
+            .map(|(x, y)| y - intercept - slope * x)
+            .collect();
+        let error_variance = errors.iter().map(|e| e.powi(2)).sum::<f64>() / n;
+
+        let theta = (1.0 - slope) / dt;
+        let mu = intercept / (1.0 - slope);
+        let sigma = (error_variance / dt).sqrt();
+
+        let residuals: Vec<f64> = errors.iter().map(|e| e / error_variance.sqrt()).collect();
+
+        let log_likelihood = -0.5 * n * ((2.0 * PI * error_variance).ln() + 1.0);
+
+        Ok(Calibration {
+            feed: OrnsteinUhlenbeck::new(prices[0], theta, mu, sigma, dt),
+            diagnostics: Diagnostics::new(&residuals, log_likelihood, 3),
+        })
+    }
+}
+
+fn validate(prices: &[f64], dt: f64) -> Result<(), ArenaError> {
+    if prices.len() < 3 {
+        return Err(ArenaError::CalibrationError(
+            "at least three prices are required".to_string(),
+        ));
+    }
+
+    if dt <= 0.0 || !dt.is_finite() {
+        return Err(ArenaError::CalibrationError(
+            "time step must be positive".to_string(),
+        ));
+    }
+
+    if prices.iter().any(|price| !price.is_finite()) {
+        return Err(ArenaError::CalibrationError(
+            "price series contains non-finite values".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Standard normal CDF, using the Abramowitz and Stegun approximation of the error function.
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.3275911 * z);
+
+    let polynomial = t
+        * (0.254829592
+            + t * (-0.284496736 + t * (1.421413741 + t * (-1.453152027 + t * 1.061405429))));
+    let erf = 1.0 - polynomial * (-z * z).exp();
+
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::{rngs::StdRng, SeedableRng};
+    use rand_distr::{Distribution, Normal};
+
+    use super::*;
+
+    /// Standard normal draws from a fixed seed, so that fixtures are the same on every run.
+    fn shocks(count: usize) -> impl Iterator<Item = f64> {
+        let mut rng = StdRng::seed_from_u64(42);
+        let normal = Normal::new(0.0, 1.0).unwrap();
+
+        (0..count).map(move |_| normal.sample(&mut rng))
+    }
+
+    /// Path of a [`GeometricBrownianMotion`], discretized as in [`Feed::step`].
+    fn geometric_brownian_motion(initial_value: f64, mu: f64, sigma: f64, dt: f64) -> Vec<f64> {
+        shocks(20000)
+            .scan(initial_value, |value, shock| {
+                *value *= ((mu - 0.5 * sigma.powi(2)) * dt + sigma * dt.sqrt() * shock).exp();
+                Some(*value)
+            })
+            .collect()
+    }
+
+    /// Path of an [`OrnsteinUhlenbeck`] process, discretized as in [`Feed::step`].
+    fn ornstein_uhlenbeck(
+        initial_value: f64,
+        theta: f64,
+        mu: f64,
+        sigma: f64,
+        dt: f64,
+    ) -> Vec<f64> {
+        shocks(20000)
+            .scan(initial_value, |value, shock| {
+                *value += theta * (mu - *value) * dt + sigma * dt.sqrt() * shock;
+                Some(*value)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_calibrate_geometric_brownian_motion() {
+        let prices = geometric_brownian_motion(1.0, 0.05, 0.2, 0.01);
+
+        let calibration = GeometricBrownianMotion::calibrate(&prices, 0.01).unwrap();
+
+        assert!((calibration.feed.sigma - 0.2).abs() < 0.01);
+        assert!(calibration.diagnostics.residual_mean.abs() < 1e-9);
+        assert!(calibration.diagnostics.ks_statistic < 0.05);
+    }
+
+    #[test]
+    fn test_calibrate_ornstein_uhlenbeck() {
+        let prices = ornstein_uhlenbeck(1.0, 2.0, 1.5, 0.1, 0.01);
+
+        let calibration = OrnsteinUhlenbeck::calibrate(&prices, 0.01).unwrap();
+
+        assert!((calibration.feed.sigma - 0.1).abs() < 0.01);
+        assert!((calibration.feed.theta - 2.0).abs() < 0.5);
+        assert!((calibration.feed.mu - 1.5).abs() < 0.1);
+        assert!(calibration.diagnostics.autocorrelation.abs() < 0.05);
+    }
+}
@@ -0,0 +1,261 @@
+use std::collections::VecDeque;
+
+use rand::thread_rng;
+use rand_distr::{Distribution, Normal};
+
+/// Fits the parameters of built-in feeds to historical price data.
+pub mod calibration;
+
+/// Resamples historical returns to generate synthetic price paths.
+pub mod bootstrap;
+
+/// Represents an arbitrary price feed.
+pub trait Feed {
+    /// Returns the current value of the feed.
+    fn current_value(&self) -> f64;
+
+    /// Returns the current simulated time of the feed.
+    fn current_time(&self) -> f64;
+
+    /// Advances the feed by one time step and returns the new value.
+    fn step(&mut self) -> f64;
+
+    /// Returns the value the feed will take `ahead` steps from now, if it is known in advance.
+    fn peek(&self, _ahead: usize) -> Option<f64> {
+        None
+    }
+}
+
+#[derive(Debug)]
+/// Implementation of an Ornstein-Uhlenbeck process using a Euler-Maruyama discretization scheme.
+pub struct OrnsteinUhlenbeck {
+    current_value: f64,
+
+    /// Current time, incremented with each step by the time step `dt`.
+    current_time: f64,
+
+    /// Mean reversion rate.
+    pub theta: f64,
+
+    /// Long-term mean.
+    pub mu: f64,
+
+    /// Volatility.
+    pub sigma: f64,
+
+    /// Time step.
+    pub dt: f64,
+}
+
+impl OrnsteinUhlenbeck {
+    /// Public constructor function for a new [`OrnsteinUhlenbeck`].
+    pub fn new(initial_value: f64, theta: f64, mu: f64, sigma: f64, dt: f64) -> Self {
+        OrnsteinUhlenbeck {
+            current_value: initial_value,
+            current_time: 0.0,
+            theta,
+            mu,
+            sigma,
+            dt,
+        }
+    }
+}
+
+impl Feed for OrnsteinUhlenbeck {
+    fn current_value(&self) -> f64 {
+        self.current_value
+    }
+
+    fn current_time(&self) -> f64 {
+        self.current_time
+    }
+
+    fn step(&mut self) -> f64 {
+        let mut rng = thread_rng();
+        let normal = Normal::new(0.0, 1.0).unwrap();
+
+        let drift = self.theta * (self.mu - self.current_value) * self.dt;
+        let randomness = self.sigma * self.dt.sqrt() * normal.sample(&mut rng);
+
+        self.current_value += drift + randomness;
+        self.current_time += self.dt;
+        self.current_value
+    }
+}
+
+#[derive(Debug)]
+/// Implementation of a geometric Brownian motion using a Euler-Maruyama discretization scheme.
+pub struct GeometricBrownianMotion {
+    /// The initial value of the process.
+    pub initial_value: f64,
+
+    /// The current value of the process.
+    pub current_value: f64,
+
+    /// The current time in the process, incremented with each step by the time step `dt`.
+    pub current_time: f64,
+
+    /// The drift coefficient.
+    pub mu: f64,
+
+    /// The volatility coefficient.
+    pub sigma: f64,
+
+    /// The time step size used for advancing the process.
+    pub dt: f64,
+}
+
+impl GeometricBrownianMotion {
+    /// Public constructor function for a new [`GeometricBrownianMotion`].
+    pub fn new(initial_value: f64, mu: f64, sigma: f64, dt: f64) -> Self {
+        GeometricBrownianMotion {
+            initial_value,
+            current_value: initial_value,
+            current_time: 0.0,
+            mu,
+            sigma,
+            dt,
+        }
+    }
+}
+
+impl Feed for GeometricBrownianMotion {
+    fn current_value(&self) -> f64 {
+        self.current_value
+    }
+
+    fn current_time(&self) -> f64 {
+        self.current_time
+    }
+
+    fn step(&mut self) -> f64 {
+        let mut rng = thread_rng();
+        let normal = Normal::new(0.0, 1.0).unwrap();
+
+        let wiener_process = normal.sample(&mut rng) * self.dt.sqrt();
+
+        let drift = (self.mu - 0.5 * self.sigma.powi(2)) * self.dt;
+
+        let volatility = self.sigma * wiener_process;
+
+        let change = drift + volatility;
+
+        self.current_value *= (change).exp();
+        self.current_time += self.dt;
+        self.current_value
+    }
+}
+
+/// Wrapper around a [`Feed`] that generates values a fixed number of steps ahead of time, so that they can be peeked at before the feed reaches them.
+pub struct Lookahead {
+    inner: Box<dyn Feed>,
+
+    /// Current value and time of the feed.
+    current: (f64, f64),
+
+    /// Upcoming values and times of the feed, nearest first.
+    upcoming: VecDeque<(f64, f64)>,
+}
+
+impl Lookahead {
+    /// Public constructor function for a new [`Lookahead`] that knows the next `horizon` values of `inner`.
+    pub fn new(mut inner: Box<dyn Feed>, horizon: usize) -> Self {
+        let current = (inner.current_value(), inner.current_time());
+
+        let upcoming = (0..horizon)
+            .map(|_| (inner.step(), inner.current_time()))
+            .collect();
+
+        Self {
+            inner,
+            current,
+            upcoming,
+        }
+    }
+}
+
+impl Feed for Lookahead {
+    fn current_value(&self) -> f64 {
+        self.current.0
+    }
+
+    fn current_time(&self) -> f64 {
+        self.current.1
+    }
+
+    fn step(&mut self) -> f64 {
+        self.upcoming
+            .push_back((self.inner.step(), self.inner.current_time()));
+        self.current = self.upcoming.pop_front().unwrap();
+        self.current.0
+    }
+
+    fn peek(&self, ahead: usize) -> Option<f64> {
+        match ahead {
+            0 => Some(self.current.0),
+            _ => self.upcoming.get(ahead - 1).map(|(value, _)| *value),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Feed whose value is ten times its time, which advances by one each step.
+    struct Counter(f64);
+
+    impl Feed for Counter {
+        fn current_value(&self) -> f64 {
+            self.0 * 10.0
+        }
+
+        fn current_time(&self) -> f64 {
+            self.0
+        }
+
+        fn step(&mut self) -> f64 {
+            self.0 += 1.0;
+            self.current_value()
+        }
+    }
+
+    #[test]
+    fn test_lookahead_peek() {
+        let mut feed = Lookahead::new(Box::new(Counter(0.0)), 3);
+
+        assert_eq!(feed.peek(0), Some(0.0));
+        assert_eq!(feed.peek(1), Some(10.0));
+        assert_eq!(feed.peek(3), Some(30.0));
+        assert_eq!(feed.peek(4), None);
+
+        // values peeked at are the ones the feed then steps through
+        let peeked: Vec<f64> = (1..=3).map(|ahead| feed.peek(ahead).unwrap()).collect();
+        let stepped: Vec<f64> = (0..3).map(|_| feed.step()).collect();
+
+        assert_eq!(peeked, stepped);
+        assert_eq!(feed.peek(3), Some(60.0));
+    }
+
+    #[test]
+    fn test_lookahead_time_alignment() {
+        let mut feed = Lookahead::new(Box::new(Counter(0.0)), 2);
+
+        assert_eq!(feed.current_time(), 0.0);
+
+        for step in 1..=5 {
+            let value = feed.step();
+
+            // the reported time is that of the current value, not of the inner feed running ahead
+            assert_eq!(feed.current_time(), step as f64);
+            assert_eq!(feed.current_value(), value);
+            assert_eq!(value, step as f64 * 10.0);
+        }
+
+        let mut feed = Lookahead::new(Box::new(Counter(0.0)), 0);
+
+        assert_eq!(feed.peek(1), None);
+        assert_eq!(feed.step(), 10.0);
+        assert_eq!(feed.current_time(), 1.0);
+    }
+}
@@ -13,6 +13,9 @@ pub mod feed;
 /// Defines the base strategy trait.
 pub mod strategy;
 
+/// Defines the base agent trait for market participants other than liquidity providers.
+pub mod agent;
+
 /// Defines core simulation logic types, such as an [`Arbitrageur`].
 pub mod engine;
 
@@ -21,7 +24,7 @@ pub mod error;
 use alloy::{
     network::{Ethereum, EthereumWallet},
     node_bindings::{Anvil, AnvilInstance},
-    primitives::{Address, Bytes, Signed, Uint},
+    primitives::{Address, Bytes, Signed, Uint, U256},
     providers::{
         fillers::{ChainIdFiller, FillProvider, GasFiller, JoinFill, NonceFiller, WalletFiller},
         Identity, RootProvider,
@@ -29,16 +32,45 @@ use alloy::{
     transports::http::{Client, Http},
 };
 
+#[cfg(feature = "columnar")]
+pub use crate::engine::columnar::{write_log_messages, ColumnarFormat, ColumnarWriter};
+#[cfg(feature = "metrics")]
+pub use crate::engine::metrics::MetricsExporter;
 use crate::types::controller::ArenaController::PoolKey;
 pub use crate::{
+    agent::{
+        hawkes::{HawkesProcess, HawkesTrader},
+        informed::InformedTrader,
+        jit::{JitLiquidityProvider, LiquidityRecord},
+        noise::{NoiseTrader, SizeDistribution},
+        searcher::SandwichSearcher,
+        Agent, Participant,
+    },
     arena::{Arena, ArenaBuilder},
     config::Config,
     engine::{
-        arbitrageur::{Arbitrageur, EmptyArbitrageur},
-        inspector::{EmptyInspector, Inspector, LogMessage, Logger},
+        arbitrageur::{
+            ArbitrageRecord, Arbitrageur, Competition, EmptyArbitrageur, FeeAwareArbitrageur,
+            FixedArbitrageur, OptimalArbitrageur, Throttle, ThrottledArbitrageur,
+        },
+        inspector::{
+            EmptyInspector, FanoutInspector, Inspector, LogMessage, Logger, ScopedInspector,
+        },
+        mempool::{
+            Action, FifoOrdering, InclusionRecord, Mempool, OrderingPolicy, PendingTransaction,
+            PriorityFeeOrdering, RandomOrdering,
+        },
+        position::Position,
+        record::{Record, RecordWriter, Value},
+        statistics::{RollingStatistics, Statistics},
+        ticks::{LiquidityBucket, TickInfo},
         Engine,
     },
-    feed::{Feed, GeometricBrownianMotion, OrnsteinUhlenbeck},
+    feed::{
+        bootstrap::BlockBootstrap,
+        calibration::{Calibrate, Calibration, Diagnostics},
+        Feed, GeometricBrownianMotion, Lookahead, OrnsteinUhlenbeck,
+    },
     strategy::Strategy,
 };
 
@@ -63,6 +95,90 @@ mod types {
             "src/artifacts/ArenaController.json"
         }
     }
+
+    pub mod exchange {
+        use alloy_sol_macro::sol;
+        sol! {
+            #[sol(rpc)]
+            #[derive(Debug)]
+            interface LiquidExchange {
+                function price() external view returns (uint256);
+                function swap(address tokenIn, uint256 amountIn) external;
+            }
+        }
+    }
+
+    pub mod fetcher {
+        use alloy_sol_macro::sol;
+        sol! {
+            #[sol(rpc)]
+            #[derive(Debug)]
+            interface Fetcher {
+                function getSlot0(address manager, bytes32 poolId) external view returns (uint160 sqrtPriceX96, int24 tick, uint24 protocolFee, uint24 lpFee);
+                function getTickInfo(address manager, bytes32 poolId, int24 tick) external view returns (uint128 liquidityGross, int128 liquidityNet, uint256 feeGrowthOutside0X128, uint256 feeGrowthOutside1X128);
+            }
+        }
+    }
+
+    pub mod manager {
+        use alloy_sol_macro::sol;
+        sol! {
+            #[sol(rpc)]
+            #[derive(Debug)]
+            interface IPoolManager {
+                function extsload(bytes32 startSlot, uint256 nSlots) external view returns (bytes32[] memory values);
+            }
+        }
+    }
+
+    pub mod router {
+        use alloy_sol_macro::sol;
+        sol! {
+            #[sol(rpc)]
+            #[derive(Debug)]
+            contract PoolSwapTest {
+                struct PoolKey {
+                    address currency0;
+                    address currency1;
+                    uint24 fee;
+                    int24 tickSpacing;
+                    address hooks;
+                }
+
+                struct SwapParams {
+                    bool zeroForOne;
+                    int256 amountSpecified;
+                    uint160 sqrtPriceLimitX96;
+                }
+
+                struct TestSettings {
+                    bool takeClaims;
+                    bool settleUsingBurn;
+                }
+
+                function swap(
+                    PoolKey memory key,
+                    SwapParams memory params,
+                    TestSettings memory testSettings,
+                    bytes memory hookData
+                ) external payable returns (int256 delta);
+            }
+        }
+    }
+
+    pub mod token {
+        use alloy_sol_macro::sol;
+        sol! {
+            #[sol(rpc)]
+            #[derive(Debug)]
+            interface ArenaToken {
+                function mint(address receiver, uint256 amount) external returns (bool);
+                function approve(address spender, uint256 amount) external returns (bool);
+                function allowance(address owner, address spender) external view returns (uint256);
+                function balanceOf(address owner) external view returns (uint256);
+            }
+        }
+    }
 }
 
 /// A signal that is passed to a [`Strategy`] to provide information about the current state of the pool.
@@ -71,15 +187,33 @@ pub struct Signal {
     /// Current theoretical value of the pool.
     pub lex_price: Uint<256, 4>,
 
-    /// Current step of the simulation.
+    /// Current step of the simulation, or `None` during initialization.
     pub step: Option<usize>,
 
+    /// Current simulated time of the price feed.
+    pub time: f64,
+
     /// Current tick of the pool.
     pub tick: Signed<24, 1>,
 
     /// Current price of the pool.
     pub sqrt_price_x96: Uint<160, 3>,
 
+    /// Active liquidity of the pool at the current tick.
+    pub liquidity: u128,
+
+    /// Global fee growth of currency0 per unit of liquidity, as a Q128.128.
+    pub fee_growth_global0_x128: U256,
+
+    /// Global fee growth of currency1 per unit of liquidity, as a Q128.128.
+    pub fee_growth_global1_x128: U256,
+
+    /// Protocol fee of the pool, with the fee for each swap direction packed into 12 bits.
+    pub protocol_fee: Uint<24, 1>,
+
+    /// LP fee charged by the pool on each swap, in hundredths of a bip. Unlike the fee in the pool key, which only holds the dynamic fee flag for dynamic fee pools, this is the fee currently charged.
+    pub lp_fee: Uint<24, 1>,
+
     /// Pool manager.
     pub manager: Address,
 
@@ -92,6 +226,10 @@ pub struct Signal {
     /// Current value of the price feed.
     pub current_value: f64,
 
+    /// Upcoming values of the price feed, for the next steps in order.
+    /// Only populated when an [`Agent`] requests a lookahead, and empty otherwise.
+    pub forecast: Vec<f64>,
+
     /// The arena controller.
     pub controller: Address,
 }
@@ -102,33 +240,63 @@ impl Signal {
     pub fn new(
         lex_price: Uint<256, 4>,
         step: Option<usize>,
+        time: f64,
         tick: Signed<24, 1>,
         sqrt_price_x96: Uint<160, 3>,
+        liquidity: u128,
+        fee_growth_global0_x128: U256,
+        fee_growth_global1_x128: U256,
+        protocol_fee: Uint<24, 1>,
+        lp_fee: Uint<24, 1>,
         manager: Address,
         pool: PoolKey,
         fetcher: Address,
         current_value: f64,
+        forecast: Vec<f64>,
         controller: Address,
     ) -> Self {
         Self {
             lex_price,
             step,
+            time,
             tick,
             sqrt_price_x96,
+            liquidity,
+            fee_growth_global0_x128,
+            fee_growth_global1_x128,
+            protocol_fee,
+            lp_fee,
             manager,
             pool,
             fetcher,
             current_value,
+            forecast,
             controller,
         }
     }
+
+    /// Price of token0 in terms of token1 on the pool.
+    pub fn pool_price(&self) -> f64 {
+        engine::math::sqrt_price_x96_to_price(Uint::from(self.sqrt_price_x96))
+    }
+
+    /// Price of token0 in terms of token1 on the liquid exchange.
+    pub fn exchange_price(&self) -> f64 {
+        let lex_price: f64 = self.lex_price.into();
+
+        lex_price / 1e18
+    }
+
+    /// Relative deviation of the pool price from the liquid exchange price.
+    pub fn mispricing(&self) -> f64 {
+        (self.pool_price() - self.exchange_price()).abs() / self.exchange_price()
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use alloy::primitives::{Signed, Uint, I256};
     use async_trait::async_trait;
-    use rug::{ops::Pow, Float};
 
     use super::*;
     use crate::{
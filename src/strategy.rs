@@ -3,6 +3,8 @@ use async_trait::async_trait;
 use super::*;
 
 /// Represents a strategy that can be run in an [`Arena`].
+///
+/// Each strategy is given its own [`ScopedInspector`](crate::engine::inspector::ScopedInspector) handle to the arena's inspectors, which tags everything it logs with the strategy's name.
 #[async_trait]
 pub trait Strategy<V> {
     /// Initialization function for ths strategy to be run upon simulation startup.
@@ -22,4 +24,9 @@ pub trait Strategy<V> {
         inspector: &mut Box<dyn Inspector<V>>,
         engine: Engine,
     );
+
+    /// Name the strategy's logs are tagged with. Defaults to `strategy-{index}`, by the order it was added to the arena.
+    fn name(&self) -> Option<String> {
+        None
+    }
 }